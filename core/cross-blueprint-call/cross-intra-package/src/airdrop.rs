@@ -1,13 +1,72 @@
+use scrypto::api::ClientObjectApi;
+use scrypto::engine::scrypto_env::ScryptoEnv;
 use scrypto::prelude::*;
 
 // This is a simple Airdrop blueprint. All components instantiated from it will initially
 // hold 1000 FreeToken within a vault. When the `free_token` method is called, 1 FreeToken will be
 // taken from the vault and returned to the caller.
+//
+// An airdrop may also be restricted to the holders of an NFT collection. In that case, holders
+// call `claim_for_holders` with a proof of their NFTs and receive `drop_amount` FreeToken for each
+// NFT that has not been used to claim before.
+//
+// A tiered airdrop rewards loyal holders of a snapshot collection: each snapshot NFT records since
+// when its owner has held the snapshotted asset, and the longer that is, the more FreeToken it can
+// claim through `claim_tiered`.
+//
+// A vesting airdrop allocates an amount of FreeToken to each NFT of a collection, which vests
+// linearly over `vesting_duration` epochs from `start_epoch`. Holders call `claim_vesting` with a
+// proof of their NFTs whenever they like and receive what has vested since their last claim.
+//
+// An airdrop instantiated with an admin badge lets the admin push tokens directly to accounts. If
+// its token was created recallable, the admin can also claw back tokens that were pushed by mistake.
+
+// The data that the NFTs of the snapshot collection of a tiered airdrop must have
+#[derive(NonFungibleData, ScryptoSbor)]
+pub struct SnapshotData {
+    // The epoch number since which the owner has held the snapshotted asset
+    pub held_since: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ClawbackEvent {
+    vault: InternalAddress,
+    amount: Decimal,
+}
 
 #[blueprint]
+#[events(ClawbackEvent)]
 mod airdrop {
+    enable_method_auth! {
+        roles {
+            admin => updatable_by: [];
+        },
+        methods {
+            push_tokens => restrict_to: [admin];
+            clawback => restrict_to: [admin];
+            free_token => PUBLIC;
+            claim_for_holders => PUBLIC;
+            claim_tiered => PUBLIC;
+            claim_vesting => PUBLIC;
+        }
+    }
+
     struct Airdrop {
         tokens: Vault,
+        eligible_collection: Option<ResourceAddress>,
+        drop_amount: Decimal,
+        max_nfts_per_claim: u64,
+        claimed: KeyValueStore<NonFungibleGlobalId, ()>,
+        recallable: bool,
+        // For tiered airdrops, the multipliers of the drop amount by the minimum number of epochs
+        // that a snapshot NFT must have been held for, in ascending order
+        tiers: Vec<(u64, Decimal)>,
+        // For vesting airdrops, the start epoch and the duration in epochs of the vesting schedule
+        vesting: Option<(u64, u64)>,
+        // For vesting airdrops, the total allocation of each NFT of the eligible collection
+        allocations: KeyValueStore<NonFungibleLocalId, Decimal>,
+        // For vesting airdrops, the part of its allocation that each NFT has already claimed
+        vested_claimed: KeyValueStore<NonFungibleLocalId, Decimal>,
     }
 
     impl Airdrop {
@@ -23,7 +82,341 @@ mod airdrop {
             // not callable from outside. In this case, it has to be owned by a particular component. Only that
             // component will be able to call methods on it. You can see an example of this in `intra_package.rs`
 
-            return Self {
+            return Self::new(None, Decimal::zero(), 0, rule!(deny_all), Vec::new()).instantiate();
+        }
+
+        pub fn instantiate_holder_airdrop(
+            eligible_collection: ResourceAddress,
+            drop_amount: Decimal,
+            max_nfts_per_claim: u64,
+        ) -> Global<Airdrop> {
+            assert!(
+                drop_amount > Decimal::zero(),
+                "The drop amount must be positive"
+            );
+            assert!(
+                max_nfts_per_claim > 0,
+                "At least one NFT must be claimable per claim"
+            );
+
+            return Self::new(
+                Some(eligible_collection),
+                drop_amount,
+                max_nfts_per_claim,
+                rule!(deny_all),
+                Vec::new(),
+            )
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize();
+        }
+
+        // Each snapshot NFT claims `base_amount` multiplied by the multiplier of the highest tier
+        // whose minimum holding period it has reached, or just `base_amount` if it reached none.
+        pub fn instantiate_tiered_airdrop(
+            snapshot_collection: ResourceAddress,
+            base_amount: Decimal,
+            max_nfts_per_claim: u64,
+            mut tiers: Vec<(u64, Decimal)>,
+        ) -> Global<Airdrop> {
+            assert!(
+                base_amount > Decimal::zero(),
+                "The base amount must be positive"
+            );
+            assert!(
+                max_nfts_per_claim > 0,
+                "At least one NFT must be claimable per claim"
+            );
+            assert!(!tiers.is_empty(), "At least one tier is required");
+            assert!(
+                tiers
+                    .iter()
+                    .all(|(_, multiplier)| *multiplier >= Decimal::one()),
+                "Tier multipliers can't be smaller than one"
+            );
+            tiers.sort_by_key(|(min_held_epochs, _)| *min_held_epochs);
+
+            return Self::new(
+                Some(snapshot_collection),
+                base_amount,
+                max_nfts_per_claim,
+                rule!(deny_all),
+                tiers,
+            )
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize();
+        }
+
+        // The allocation of each NFT vests linearly from `start_epoch` until `vesting_duration`
+        // epochs later, after which all of it can be claimed.
+        pub fn instantiate_vesting_airdrop(
+            eligible_collection: ResourceAddress,
+            allocations: Vec<(NonFungibleLocalId, Decimal)>,
+            start_epoch: u64,
+            vesting_duration: u64,
+        ) -> Global<Airdrop> {
+            assert!(
+                vesting_duration > 0,
+                "The vesting duration must be positive"
+            );
+            assert!(
+                allocations
+                    .iter()
+                    .all(|(_, allocation)| *allocation > Decimal::zero()),
+                "Allocations must be positive"
+            );
+
+            let mut airdrop = Self::new(
+                Some(eligible_collection),
+                Decimal::zero(),
+                0,
+                rule!(deny_all),
+                Vec::new(),
+            );
+            let mut total_allocation = Decimal::zero();
+            for (local_id, allocation) in allocations {
+                assert!(
+                    airdrop.allocations.get(&local_id).is_none(),
+                    "{} is allocated more than once",
+                    local_id
+                );
+                total_allocation = total_allocation.checked_add(allocation).unwrap();
+                airdrop.allocations.insert(local_id, allocation);
+            }
+            assert!(
+                total_allocation <= airdrop.tokens.amount(),
+                "The allocations exceed the {} FreeToken of the airdrop",
+                airdrop.tokens.amount()
+            );
+            airdrop.vesting = Some((start_epoch, vesting_duration));
+
+            return airdrop
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+        }
+
+        pub fn instantiate_admin_airdrop(recallable: bool) -> (Global<Airdrop>, FungibleBucket) {
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(Airdrop::blueprint_id());
+
+            let admin_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .mint_initial_supply(1);
+
+            // Only this component may recall the token, and only when the admin calls `clawback`
+            let recall_rule = if recallable {
+                rule!(require(global_caller(component_address)))
+            } else {
+                rule!(deny_all)
+            };
+
+            let airdrop = Self::new(None, Decimal::zero(), 0, recall_rule, Vec::new())
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .roles(roles!(
+                    admin => rule!(require(admin_badge.resource_address()));
+                ))
+                .with_address(address_reservation)
+                .globalize();
+
+            (airdrop, admin_badge)
+        }
+
+        pub fn free_token(&mut self) -> Bucket {
+            // Airdrops restricted to the holders of a collection are only claimable through their
+            // own claim method
+            assert!(
+                self.eligible_collection.is_none(),
+                "This airdrop is restricted to the holders of a collection, use its claim method to claim it"
+            );
+
+            // Take 1 FreeToken and return
+            self.tokens.take(1)
+        }
+
+        pub fn claim_for_holders(&mut self, holder_proof: Proof) -> Bucket {
+            assert!(
+                self.tiers.is_empty(),
+                "This airdrop is tiered, use claim_tiered to claim it"
+            );
+            assert!(
+                self.vesting.is_none(),
+                "This airdrop vests, use claim_vesting to claim it"
+            );
+            let eligible_collection = self
+                .eligible_collection
+                .expect("This airdrop is not restricted to the holders of a collection");
+            assert_eq!(
+                holder_proof.resource_address(),
+                eligible_collection,
+                "Only holders of {:?} are eligible for this airdrop",
+                eligible_collection
+            );
+            let holder_proof = holder_proof.check(eligible_collection);
+
+            // Every NFT may only be used to claim once, and at most `max_nfts_per_claim` NFTs
+            // are counted per claim.
+            let mut claimed_nfts: u64 = 0;
+            for local_id in holder_proof.as_non_fungible().non_fungible_local_ids() {
+                if claimed_nfts == self.max_nfts_per_claim {
+                    break;
+                }
+
+                let global_id = NonFungibleGlobalId::new(eligible_collection, local_id);
+                if self.claimed.get(&global_id).is_none() {
+                    self.claimed.insert(global_id, ());
+                    claimed_nfts += 1;
+                }
+            }
+            assert!(
+                claimed_nfts > 0,
+                "The NFTs provided have already been used to claim this airdrop"
+            );
+
+            self.tokens
+                .take(self.drop_amount.checked_mul(claimed_nfts).unwrap())
+        }
+
+        pub fn claim_tiered(&mut self, holder_proof: Proof) -> Bucket {
+            assert!(!self.tiers.is_empty(), "This airdrop is not tiered");
+            let snapshot_collection = self.eligible_collection.unwrap();
+            assert_eq!(
+                holder_proof.resource_address(),
+                snapshot_collection,
+                "Only holders of {:?} are eligible for this airdrop",
+                snapshot_collection
+            );
+            let holder_proof = holder_proof.check(snapshot_collection);
+
+            // Every snapshot NFT may only be used to claim once, and at most `max_nfts_per_claim`
+            // NFTs are counted per claim.
+            let current_epoch = Runtime::current_epoch().number();
+            let mut claimed_nfts: u64 = 0;
+            let mut amount = Decimal::zero();
+            for nft in holder_proof
+                .as_non_fungible()
+                .non_fungibles::<SnapshotData>()
+            {
+                if claimed_nfts == self.max_nfts_per_claim {
+                    break;
+                }
+
+                let global_id =
+                    NonFungibleGlobalId::new(snapshot_collection, nft.local_id().clone());
+                if self.claimed.get(&global_id).is_none() {
+                    self.claimed.insert(global_id, ());
+                    claimed_nfts += 1;
+
+                    let held_epochs = current_epoch.saturating_sub(nft.data().held_since);
+                    let multiplier = self
+                        .tiers
+                        .iter()
+                        .rev()
+                        .find(|(min_held_epochs, _)| held_epochs >= *min_held_epochs)
+                        .map_or(Decimal::one(), |(_, multiplier)| *multiplier);
+                    amount = amount
+                        .checked_add(self.drop_amount.checked_mul(multiplier).unwrap())
+                        .unwrap();
+                }
+            }
+            assert!(
+                claimed_nfts > 0,
+                "The NFTs provided have already been used to claim this airdrop"
+            );
+
+            self.tokens.take(amount)
+        }
+
+        // Releases the part of the allocation of every NFT in `holder_proof` that has vested since it
+        // was last claimed.
+        pub fn claim_vesting(&mut self, holder_proof: Proof) -> Bucket {
+            let (start_epoch, vesting_duration) = self.vesting.expect("This airdrop does not vest");
+            let eligible_collection = self.eligible_collection.unwrap();
+            assert_eq!(
+                holder_proof.resource_address(),
+                eligible_collection,
+                "Only holders of {:?} are eligible for this airdrop",
+                eligible_collection
+            );
+            let holder_proof = holder_proof.check(eligible_collection);
+
+            let vested_epochs = Runtime::current_epoch()
+                .number()
+                .saturating_sub(start_epoch)
+                .min(vesting_duration);
+            let mut amount = Decimal::zero();
+            for local_id in holder_proof.as_non_fungible().non_fungible_local_ids() {
+                let allocation = match self.allocations.get(&local_id) {
+                    Some(allocation) => *allocation,
+                    None => continue,
+                };
+                let vested = allocation
+                    .checked_mul(vested_epochs)
+                    .unwrap()
+                    .checked_div(vesting_duration)
+                    .unwrap();
+                let claimed = self
+                    .vested_claimed
+                    .get(&local_id)
+                    .map_or(Decimal::zero(), |claimed| *claimed);
+
+                amount = amount
+                    .checked_add(vested.checked_sub(claimed).unwrap())
+                    .unwrap();
+                self.vested_claimed.insert(local_id, vested);
+            }
+            assert!(
+                amount > Decimal::zero(),
+                "Nothing has vested for the NFTs provided since their last claim"
+            );
+
+            self.tokens.take(amount)
+        }
+
+        pub fn push_tokens(&mut self, account: ComponentAddress, amount: Decimal) {
+            let mut account: Global<Account> = Global::from(account);
+            account.try_deposit_or_abort(self.tokens.take(amount), None);
+        }
+
+        // Takes `amount` tokens back from the `vault` that they were pushed to.
+        // A component can't look up the vaults of an account, so the address of the vault holding
+        // the token has to be looked up off-ledger (e.g. through the Gateway) and passed in.
+        pub fn clawback(&mut self, vault: InternalAddress, amount: Decimal) {
+            assert!(
+                self.recallable,
+                "Clawback is only possible if the token was created recallable at instantiation"
+            );
+
+            // Recall is a direct access method: it is invoked on a vault owned by another
+            // component, so Scrypto offers no `Vault` handle for it and the call has to go
+            // through the engine with `call_direct_access_method`.
+            let recalled: Bucket = scrypto_decode(
+                &ScryptoEnv
+                    .call_direct_access_method(
+                        vault.as_node_id(),
+                        VAULT_RECALL_IDENT,
+                        scrypto_args!(amount),
+                    )
+                    .unwrap(),
+            )
+            .unwrap();
+            self.tokens.put(recalled);
+
+            Runtime::emit_event(ClawbackEvent { vault, amount });
+        }
+
+        fn new(
+            eligible_collection: Option<ResourceAddress>,
+            drop_amount: Decimal,
+            max_nfts_per_claim: u64,
+            recall_rule: AccessRule,
+            tiers: Vec<(u64, Decimal)>,
+        ) -> Self {
+            let recallable = recall_rule != rule!(deny_all);
+
+            Self {
                 tokens: Vault::with_bucket(
                     ResourceBuilder::new_fungible(OwnerRole::None)
                         .divisibility(DIVISIBILITY_MAXIMUM)
@@ -32,16 +425,23 @@ mod airdrop {
                                 "name" => "Free Token".to_string(), locked;
                             }
                         ))
+                        .recall_roles(recall_roles!(
+                            recaller => recall_rule;
+                            recaller_updater => rule!(deny_all);
+                        ))
                         .mint_initial_supply(1000)
                         .into(),
                 ),
+                eligible_collection,
+                drop_amount,
+                max_nfts_per_claim,
+                claimed: KeyValueStore::new(),
+                recallable,
+                tiers,
+                vesting: None,
+                allocations: KeyValueStore::new(),
+                vested_claimed: KeyValueStore::new(),
             }
-            .instantiate();
-        }
-
-        pub fn free_token(&mut self) -> Bucket {
-            // Take 1 FreeToken and return
-            self.tokens.take(1)
         }
     }
 }
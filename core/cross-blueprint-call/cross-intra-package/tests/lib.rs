@@ -1 +1,545 @@
-// TODO: Update Tests
+use radix_engine::transaction::TransactionReceipt;
+use scrypto::prelude::*;
+use scrypto_unit::*;
+use transaction::{builder::ManifestBuilder, prelude::TransactionManifestV1};
+
+#[derive(ScryptoSbor, ManifestSbor, NonFungibleData)]
+struct SnapshotData {
+    held_since: u64,
+}
+
+pub struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    public_key: Secp256k1PublicKey,
+    account_address: ComponentAddress,
+    package_address: PackageAddress,
+}
+
+impl TestEnvironment {
+    pub fn instantiate_test() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().build();
+
+        // Create an account
+        let (public_key, _private_key, account_address) = test_runner.new_allocated_account();
+
+        // Publish package
+        let package_address = test_runner.compile_and_publish(this_package!());
+
+        Self {
+            test_runner,
+            public_key,
+            account_address,
+            package_address,
+        }
+    }
+
+    pub fn execute(&mut self, manifest: TransactionManifestV1) -> TransactionReceipt {
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    /// Instantiates a holder airdrop and returns the addresses of the component and of the
+    /// airdropped token.
+    pub fn instantiate_holder_airdrop(
+        &mut self,
+        eligible_collection: ResourceAddress,
+        drop_amount: Decimal,
+        max_nfts_per_claim: u64,
+    ) -> (ComponentAddress, ResourceAddress) {
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                self.package_address,
+                "Airdrop",
+                "instantiate_holder_airdrop",
+                manifest_args!(eligible_collection, drop_amount, max_nfts_per_claim),
+            )
+            .build();
+        let receipt = self.execute(manifest);
+        let commit = receipt.expect_commit_success();
+
+        (
+            commit.new_component_addresses()[0],
+            commit.new_resource_addresses()[0],
+        )
+    }
+
+    /// Instantiates an admin airdrop and returns the addresses of the component, of the admin
+    /// badge and of the airdropped token.
+    pub fn instantiate_admin_airdrop(
+        &mut self,
+        recallable: bool,
+    ) -> (ComponentAddress, ResourceAddress, ResourceAddress) {
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                self.package_address,
+                "Airdrop",
+                "instantiate_admin_airdrop",
+                manifest_args!(recallable),
+            )
+            .deposit_batch(self.account_address)
+            .build();
+        let receipt = self.execute(manifest);
+        let commit = receipt.expect_commit_success();
+
+        (
+            commit.new_component_addresses()[0],
+            commit.new_resource_addresses()[0],
+            commit.new_resource_addresses()[1],
+        )
+    }
+
+    /// Calls an admin-gated method of the airdrop with a proof of the admin badge.
+    pub fn call_as_admin(
+        &mut self,
+        airdrop: ComponentAddress,
+        admin_badge: ResourceAddress,
+        method_name: &str,
+        args: ManifestArgs,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, admin_badge, dec!(1))
+            .call_method(airdrop, method_name, args)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Creates a snapshot collection in the account with one NFT per entry of `held_since`, using
+    /// integer ids starting from 1.
+    pub fn create_snapshot_collection(&mut self, held_since: Vec<u64>) -> ResourceAddress {
+        let manifest = ManifestBuilder::new()
+            .create_non_fungible_resource(
+                OwnerRole::None,
+                NonFungibleIdType::Integer,
+                false,
+                NonFungibleResourceRoles::default(),
+                metadata!(),
+                Some(
+                    held_since
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, held_since)| {
+                            (
+                                NonFungibleLocalId::integer(index as u64 + 1),
+                                SnapshotData { held_since },
+                            )
+                        })
+                        .collect::<BTreeMap<_, _>>(),
+                ),
+            )
+            .deposit_batch(self.account_address)
+            .build();
+        let receipt = self.execute(manifest);
+
+        receipt.expect_commit_success().new_resource_addresses()[0]
+    }
+
+    /// Instantiates a tiered airdrop and returns the addresses of the component and of the
+    /// airdropped token.
+    pub fn instantiate_tiered_airdrop(
+        &mut self,
+        snapshot_collection: ResourceAddress,
+        base_amount: Decimal,
+        max_nfts_per_claim: u64,
+        tiers: Vec<(u64, Decimal)>,
+    ) -> (ComponentAddress, ResourceAddress) {
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                self.package_address,
+                "Airdrop",
+                "instantiate_tiered_airdrop",
+                manifest_args!(snapshot_collection, base_amount, max_nfts_per_claim, tiers),
+            )
+            .build();
+        let receipt = self.execute(manifest);
+        let commit = receipt.expect_commit_success();
+
+        (
+            commit.new_component_addresses()[0],
+            commit.new_resource_addresses()[0],
+        )
+    }
+
+    /// Instantiates a vesting airdrop and returns the addresses of the component and of the
+    /// airdropped token.
+    pub fn instantiate_vesting_airdrop(
+        &mut self,
+        eligible_collection: ResourceAddress,
+        allocations: Vec<(NonFungibleLocalId, Decimal)>,
+        start_epoch: u64,
+        vesting_duration: u64,
+    ) -> (ComponentAddress, ResourceAddress) {
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                self.package_address,
+                "Airdrop",
+                "instantiate_vesting_airdrop",
+                manifest_args!(
+                    eligible_collection,
+                    allocations,
+                    start_epoch,
+                    vesting_duration
+                ),
+            )
+            .build();
+        let receipt = self.execute(manifest);
+        let commit = receipt.expect_commit_success();
+
+        (
+            commit.new_component_addresses()[0],
+            commit.new_resource_addresses()[0],
+        )
+    }
+
+    pub fn claim_vesting(
+        &mut self,
+        airdrop: ComponentAddress,
+        collection: ResourceAddress,
+        ids: BTreeSet<NonFungibleLocalId>,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(self.account_address, collection, ids)
+            .pop_from_auth_zone("holder_proof")
+            .call_method_with_name_lookup(airdrop, "claim_vesting", |lookup| {
+                (lookup.proof("holder_proof"),)
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn claim_tiered(
+        &mut self,
+        airdrop: ComponentAddress,
+        collection: ResourceAddress,
+        ids: BTreeSet<NonFungibleLocalId>,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(self.account_address, collection, ids)
+            .pop_from_auth_zone("holder_proof")
+            .call_method_with_name_lookup(airdrop, "claim_tiered", |lookup| {
+                (lookup.proof("holder_proof"),)
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn claim_for_holders(
+        &mut self,
+        airdrop: ComponentAddress,
+        collection: ResourceAddress,
+        ids: BTreeSet<NonFungibleLocalId>,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(self.account_address, collection, ids)
+            .pop_from_auth_zone("holder_proof")
+            .call_method_with_name_lookup(airdrop, "claim_for_holders", |lookup| {
+                (lookup.proof("holder_proof"),)
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+}
+
+#[test]
+fn eligible_holder_receives_drop_per_nft() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let collection = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account_address);
+    let (airdrop, free_token) =
+        test_environment.instantiate_holder_airdrop(collection, dec!(5), 10);
+
+    test_environment
+        .claim_for_holders(
+            airdrop,
+            collection,
+            btreeset!(
+                NonFungibleLocalId::integer(1),
+                NonFungibleLocalId::integer(2)
+            ),
+        )
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, free_token),
+        dec!(10)
+    );
+}
+
+#[test]
+fn holder_of_another_collection_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let collection = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account_address);
+    let other_collection = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account_address);
+    let (airdrop, _) = test_environment.instantiate_holder_airdrop(collection, dec!(5), 10);
+
+    let receipt = test_environment.claim_for_holders(
+        airdrop,
+        other_collection,
+        btreeset!(NonFungibleLocalId::integer(1)),
+    );
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("are eligible for this airdrop")
+    });
+}
+
+#[test]
+fn same_nft_can_not_claim_twice() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let collection = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account_address);
+    let (airdrop, _) = test_environment.instantiate_holder_airdrop(collection, dec!(5), 10);
+
+    test_environment
+        .claim_for_holders(
+            airdrop,
+            collection,
+            btreeset!(NonFungibleLocalId::integer(1)),
+        )
+        .expect_commit_success();
+    let receipt = test_environment.claim_for_holders(
+        airdrop,
+        collection,
+        btreeset!(NonFungibleLocalId::integer(1)),
+    );
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("have already been used to claim")
+    });
+}
+
+#[test]
+fn free_token_of_a_holder_airdrop_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let collection = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account_address);
+    let (airdrop, _) = test_environment.instantiate_holder_airdrop(collection, dec!(5), 10);
+
+    let manifest = ManifestBuilder::new()
+        .call_method(airdrop, "free_token", manifest_args!())
+        .deposit_batch(test_environment.account_address)
+        .build();
+    let receipt = test_environment.execute(manifest);
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("is restricted to the holders of a collection")
+    });
+}
+
+#[test]
+fn admin_can_claw_back_pushed_tokens() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (_, _, recipient) = test_environment.test_runner.new_allocated_account();
+    let (airdrop, admin_badge, free_token) = test_environment.instantiate_admin_airdrop(true);
+
+    test_environment
+        .call_as_admin(
+            airdrop,
+            admin_badge,
+            "push_tokens",
+            manifest_args!(recipient, dec!(100)),
+        )
+        .expect_commit_success();
+    let vault = test_environment
+        .test_runner
+        .get_component_vaults(recipient, free_token)[0];
+    test_environment
+        .call_as_admin(
+            airdrop,
+            admin_badge,
+            "clawback",
+            manifest_args!(InternalAddress::new_or_panic(vault.0), dec!(40)),
+        )
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(recipient, free_token),
+        dec!(60)
+    );
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(airdrop, free_token),
+        dec!(940)
+    );
+}
+
+#[test]
+fn clawback_requires_a_recallable_token() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (_, _, recipient) = test_environment.test_runner.new_allocated_account();
+    let (airdrop, admin_badge, free_token) = test_environment.instantiate_admin_airdrop(false);
+
+    test_environment
+        .call_as_admin(
+            airdrop,
+            admin_badge,
+            "push_tokens",
+            manifest_args!(recipient, dec!(100)),
+        )
+        .expect_commit_success();
+    let vault = test_environment
+        .test_runner
+        .get_component_vaults(recipient, free_token)[0];
+    let receipt = test_environment.call_as_admin(
+        airdrop,
+        admin_badge,
+        "clawback",
+        manifest_args!(InternalAddress::new_or_panic(vault.0), dec!(40)),
+    );
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("the token was created recallable at instantiation")
+    });
+}
+
+#[test]
+fn tiered_drop_grows_with_holding_duration() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .test_runner
+        .set_current_epoch(Epoch::of(10_000));
+    // Held for 0, 150 and 2000 epochs respectively
+    let collection = test_environment.create_snapshot_collection(vec![10_000, 9_850, 8_000]);
+    let (airdrop, free_token) = test_environment.instantiate_tiered_airdrop(
+        collection,
+        dec!(10),
+        10,
+        vec![(100, dec!(2)), (1_000, dec!(5))],
+    );
+
+    for (id, expected_balance) in [(1, dec!(10)), (2, dec!(30)), (3, dec!(80))] {
+        test_environment
+            .claim_tiered(
+                airdrop,
+                collection,
+                btreeset!(NonFungibleLocalId::integer(id)),
+            )
+            .expect_commit_success();
+
+        assert_eq!(
+            test_environment
+                .test_runner
+                .get_component_balance(test_environment.account_address, free_token),
+            expected_balance
+        );
+    }
+}
+
+#[test]
+fn tiered_drop_can_not_be_claimed_twice() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .test_runner
+        .set_current_epoch(Epoch::of(10_000));
+    let collection = test_environment.create_snapshot_collection(vec![8_000]);
+    let (airdrop, free_token) = test_environment.instantiate_tiered_airdrop(
+        collection,
+        dec!(10),
+        10,
+        vec![(100, dec!(2)), (1_000, dec!(5))],
+    );
+
+    test_environment
+        .claim_tiered(
+            airdrop,
+            collection,
+            btreeset!(NonFungibleLocalId::integer(1)),
+        )
+        .expect_commit_success();
+    test_environment
+        .claim_tiered(
+            airdrop,
+            collection,
+            btreeset!(NonFungibleLocalId::integer(1)),
+        )
+        .expect_commit_failure();
+
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, free_token),
+        dec!(50)
+    );
+}
+
+#[test]
+fn vesting_allocation_is_released_over_the_schedule() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .test_runner
+        .set_current_epoch(Epoch::of(1_000));
+    let collection = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account_address);
+    let (airdrop, free_token) = test_environment.instantiate_vesting_airdrop(
+        collection,
+        vec![(NonFungibleLocalId::integer(1), dec!(100))],
+        1_000,
+        100,
+    );
+
+    // A quarter of the way through the schedule, a quarter of the allocation has vested
+    test_environment
+        .test_runner
+        .set_current_epoch(Epoch::of(1_025));
+    test_environment
+        .claim_vesting(
+            airdrop,
+            collection,
+            btreeset!(NonFungibleLocalId::integer(1)),
+        )
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, free_token),
+        dec!(25)
+    );
+
+    // Nothing more vests until the epoch changes
+    test_environment
+        .claim_vesting(
+            airdrop,
+            collection,
+            btreeset!(NonFungibleLocalId::integer(1)),
+        )
+        .expect_commit_failure();
+
+    // After the full duration, the remainder is released and the claims add up to the allocation
+    test_environment
+        .test_runner
+        .set_current_epoch(Epoch::of(1_200));
+    test_environment
+        .claim_vesting(
+            airdrop,
+            collection,
+            btreeset!(NonFungibleLocalId::integer(1)),
+        )
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, free_token),
+        dec!(100)
+    );
+}
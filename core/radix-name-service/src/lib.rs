@@ -1,3 +1,6 @@
+use account_auth::assert_account_owner;
+use scrypto::api::ClientObjectApi;
+use scrypto::engine::scrypto_env::ScryptoEnv;
 use scrypto::prelude::*;
 use sha2::{Digest, Sha256};
 
@@ -11,13 +14,128 @@ struct DomainName {
 
     #[mutable]
     deposit_amount: Decimal,
+
+    #[mutable]
+    registered_epoch: Epoch,
+
+    #[mutable]
+    registrant_account: ComponentAddress,
+
+    // The number of epochs for which resolvers may cache the address, or the component's default
+    #[mutable]
+    ttl_epochs: Option<u64>,
+}
+
+// A reservation of the right to register a name, bought in the presale
+#[derive(NonFungibleData, ScryptoSbor)]
+struct NameReservation {
+    name: String,
+    paid: Decimal,
+    // The last epoch in which the reservation can be redeemed, after which the name is free and the
+    // reservation can only be refunded
+    last_valid_epoch: Epoch,
+}
+
+// The optional features of a name service
+#[derive(ScryptoSbor)]
+pub struct RnsConfig {
+    // Deposits and fees are paid in this token, or in XRD if none is given
+    pub payment_token: Option<ResourceAddress>,
+
+    // Names that are unregistered less than `min_holding_epochs` after their registration only get
+    // back their deposit minus this percentage, which is kept as a fee
+    pub early_unregister_penalty_percent: Decimal,
+    pub min_holding_epochs: u64,
+
+    // A registrant must wait this many epochs after registering a name before they can register
+    // another one. A cooldown of zero disables this.
+    pub registration_cooldown_epochs: u64,
+
+    // Callers who prove that they hold an unexpired name get this percentage off the address
+    // update and renewal fees
+    pub loyalty_discount_percent: Decimal,
+
+    // Each name can hold at most this many text records, whose values can be at most
+    // `max_value_length` bytes long
+    pub max_records_per_name: u32,
+    pub max_value_length: u32,
+
+    // If set, only whitelisted accounts can register names
+    pub whitelist_only: bool,
+
+    // An account can hold at most this many names that it registered itself. A limit of zero
+    // disables this.
+    pub max_names_per_account: u32,
+
+    // Resolvers may cache the address of a name for this many epochs, unless its owner sets a
+    // different TTL for it
+    pub default_ttl_epochs: u64,
+}
+
+// What a call of `burn_expired_names` did
+#[derive(ScryptoSbor)]
+pub struct CleanupReport {
+    pub burned_count: u64,
+    // The IDs of the NFTs of the names that were burned
+    pub names: Vec<NonFungibleLocalId>,
 }
 
 // Assuming an average epoch duration of 35 minutes, 15k epochs roughly fit into one year
 // This is a very rough estimate, of course
 const EPOCHS_PER_YEAR: u64 = 15_000;
 
+// The maximum number of years a name can be reserved or renewed for at once
+const MAX_RESERVATION_YEARS: u8 = 100;
+
+// The number of epochs after its expiry during which a name can still be renewed automatically
+// from its renewal credit. This is roughly one month.
+const GRACE_PERIOD_EPOCHS: u64 = EPOCHS_PER_YEAR / 12;
+
+// The maximum length in bytes of the key of a text record
+const MAX_RECORD_KEY_LENGTH: usize = 64;
+
+// The maximum number of watchers that can wait for a single name to become available
+const MAX_WATCHERS_PER_NAME: usize = 20;
+
+// The maximum number of names a single call of a batch method may process, which keeps batches
+// within the cost and size limits of a transaction
+const MAX_BATCH_SIZE: usize = 50;
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct NameFrozenEvent {
+    name: String,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct NameUnfrozenEvent {
+    name: String,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct PausedEvent {
+    paused: bool,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct WatchedNameAvailableEvent {
+    name_hash: u128,
+    watchers: Vec<ComponentAddress>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct AutoRenewedEvent {
+    name: String,
+    last_valid_epoch: Epoch,
+}
+
 #[blueprint]
+#[events(
+    NameFrozenEvent,
+    NameUnfrozenEvent,
+    PausedEvent,
+    WatchedNameAvailableEvent,
+    AutoRenewedEvent
+)]
 mod radix_name_service {
     enable_method_auth! {
         roles {
@@ -25,8 +143,33 @@ mod radix_name_service {
         },
         methods {
             burn_expired_names => restrict_to: [admin];
+            claim_expired_refund => PUBLIC;
+            names_held_count => PUBLIC;
+            reserve_presale => PUBLIC;
+            refund_reservation => PUBLIC;
             withdraw_fees => restrict_to: [admin];
+            withdraw_fees_to => restrict_to: [admin];
+            freeze_name => restrict_to: [admin];
+            unfreeze_name => restrict_to: [admin];
+            set_paused => restrict_to: [admin];
+            set_whitelist_only => restrict_to: [admin];
+            set_max_names_per_account => restrict_to: [admin];
+            open_presale => restrict_to: [admin];
+            close_presale => restrict_to: [admin];
+            add_to_whitelist => restrict_to: [admin];
+            remove_from_whitelist => restrict_to: [admin];
+            available_fees => PUBLIC;
             lookup_address => PUBLIC;
+            lookup_addresses => PUBLIC;
+            lookup_with_ttl => PUBLIC;
+            check_availability => PUBLIC;
+            watch_name => PUBLIC;
+            set_text_record => PUBLIC;
+            set_ttl => PUBLIC;
+            list_records => PUBLIC;
+            deposit_renewal_credit => PUBLIC;
+            auto_renew => PUBLIC;
+            verify_ownership => PUBLIC;
             register_name => PUBLIC;
             unregister_name => PUBLIC;
             update_address => PUBLIC;
@@ -36,20 +179,82 @@ mod radix_name_service {
     struct RadixNameService {
         admin_badge: ResourceAddress,
         name_resource: ResourceManager,
+        // The NFTs of names that are no longer registered, which are re-issued when the names are
+        // registered again, since the ID of a burned NFT can never be minted again
+        released_names: Vault,
         deposits: Vault,
         fees: Vault,
         deposit_per_year: Decimal,
         fee_address_update: Decimal,
         fee_renewal_per_year: Decimal,
+        payment_token: ResourceAddress,
+        frozen_names: KeyValueStore<NonFungibleLocalId, bool>,
+        paused: bool,
+        watchers: KeyValueStore<u128, Vec<ComponentAddress>>,
+        early_unregister_penalty_percent: Decimal,
+        min_holding_epochs: u64,
+        renewal_credits: KeyValueStore<NonFungibleLocalId, Vault>,
+        renewal_credit_depositors: KeyValueStore<NonFungibleLocalId, ComponentAddress>,
+        registration_cooldown_epochs: u64,
+        last_registrations: KeyValueStore<ComponentAddress, Epoch>,
+        loyalty_discount_percent: Decimal,
+        text_records: KeyValueStore<NonFungibleLocalId, BTreeMap<String, String>>,
+        max_records_per_name: u32,
+        max_value_length: u32,
+        whitelist_only: bool,
+        whitelist: KeyValueStore<ComponentAddress, ()>,
+        expired_refunds: KeyValueStore<ComponentAddress, Vault>,
+        max_names_per_account: u32,
+        names_held: KeyValueStore<ComponentAddress, u32>,
+        reservation_resource: ResourceManager,
+        presale_price: Option<Decimal>,
+        presale_reservation_epochs: u64,
+        // The current reservation of each reserved name, by name hash
+        presale_reservations: KeyValueStore<u128, NonFungibleLocalId>,
+        default_ttl_epochs: u64,
     }
 
     impl RadixNameService {
         /// Creates a new RNS instance
+        ///
+        /// The optional features of the name service are given by `config`, see `RnsConfig`.
         pub fn instantiate_rns(
             deposit_per_year: Decimal,
             fee_address_update: Decimal,
             fee_renewal_per_year: Decimal,
+            config: RnsConfig,
         ) -> (Global<RadixNameService>, FungibleBucket) {
+            let RnsConfig {
+                payment_token,
+                early_unregister_penalty_percent,
+                min_holding_epochs,
+                registration_cooldown_epochs,
+                loyalty_discount_percent,
+                max_records_per_name,
+                max_value_length,
+                whitelist_only,
+                max_names_per_account,
+                default_ttl_epochs,
+            } = config;
+            let payment_token = payment_token.unwrap_or(XRD);
+            assert!(
+                loyalty_discount_percent >= Decimal::zero()
+                    && loyalty_discount_percent <= dec!(100),
+                "The loyalty discount must be between 0 and 100 percent"
+            );
+            assert!(
+                early_unregister_penalty_percent >= Decimal::zero()
+                    && early_unregister_penalty_percent <= dec!(100),
+                "The early unregister penalty must be between 0 and 100 percent"
+            );
+            assert!(
+                matches!(
+                    ResourceManager::from_address(payment_token).resource_type(),
+                    ResourceType::Fungible { .. }
+                ),
+                "The payment token must be a fungible resource"
+            );
+
             let (address_reservation, component_address) =
                 Runtime::allocate_component_address(RadixNameService::blueprint_id());
 
@@ -73,23 +278,73 @@ mod radix_name_service {
                 burner => rule!(require(global_caller(component_address)));
                 burner_updater => rule!(deny_all);
             })
+            // Only this component may recall names, to take them back once they expired
+            .recall_roles(recall_roles! {
+                recaller => rule!(require(global_caller(component_address)));
+                recaller_updater => rule!(deny_all);
+            })
             .non_fungible_data_update_roles(non_fungible_data_update_roles! {
                 non_fungible_data_updater => rule!(require(global_caller(component_address)));
                 non_fungible_data_updater_updater => rule!(deny_all);
             })
             .create_with_no_initial_supply();
 
+            let reservation_resource =
+                ResourceBuilder::new_ruid_non_fungible::<NameReservation>(OwnerRole::None)
+                    .metadata(metadata!(
+                        init {
+                            "name" => "Domain Name Reservation".to_owned(), locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .burn_roles(burn_roles! {
+                        burner => rule!(require(global_caller(component_address)));
+                        burner_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
             let component = RadixNameService {
                 admin_badge: admin_badge.resource_address(),
                 name_resource,
-                deposits: Vault::new(XRD),
-                fees: Vault::new(XRD),
+                released_names: Vault::new(name_resource.address()),
+                deposits: Vault::new(payment_token),
+                fees: Vault::new(payment_token),
                 deposit_per_year,
                 fee_address_update,
                 fee_renewal_per_year,
+                payment_token,
+                frozen_names: KeyValueStore::new(),
+                paused: false,
+                watchers: KeyValueStore::new(),
+                early_unregister_penalty_percent,
+                min_holding_epochs,
+                renewal_credits: KeyValueStore::new(),
+                renewal_credit_depositors: KeyValueStore::new(),
+                registration_cooldown_epochs,
+                last_registrations: KeyValueStore::new(),
+                loyalty_discount_percent,
+                text_records: KeyValueStore::new(),
+                max_records_per_name,
+                max_value_length,
+                whitelist_only,
+                whitelist: KeyValueStore::new(),
+                expired_refunds: KeyValueStore::new(),
+                max_names_per_account,
+                names_held: KeyValueStore::new(),
+                reservation_resource,
+                presale_price: None,
+                presale_reservation_epochs: 0,
+                presale_reservations: KeyValueStore::new(),
+                default_ttl_epochs,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::None)
+            .roles(roles!(
+                admin => rule!(require(admin_badge.resource_address()));
+            ))
             .with_address(address_reservation)
             .globalize();
 
@@ -97,22 +352,129 @@ mod radix_name_service {
         }
 
         /// Lookup the address for a given `name`.
-        /// Panics if that name is not registered.
+        /// Panics if that name is not registered or has expired.
         pub fn lookup_address(&self, name: String) -> String {
-            let hash = Self::hash_name(name);
-
+            let id = Self::name_id(name);
+            assert!(self.is_registered(&id), "name not registered");
             let resource_manager = self.name_resource;
-            let name_data: DomainName =
-                resource_manager.get_non_fungible_data(&NonFungibleLocalId::Bytes(
-                    BytesNonFungibleLocalId::new(hash.to_be_bytes().to_vec()).unwrap(),
-                ));
+            let name_data: DomainName = resource_manager.get_non_fungible_data(&id);
+            assert!(
+                Runtime::current_epoch() <= name_data.last_valid_epoch,
+                "name expired"
+            );
 
             name_data.address.to_hex()
         }
 
+        /// Looks up the address for a given `name` along with the epoch until which resolvers may
+        /// cache it, which is the end of the name's TTL but never after the name expires.
+        /// Panics if that name is not registered or has expired.
+        pub fn lookup_with_ttl(&self, name: String) -> (ComponentAddress, u64) {
+            let id = Self::name_id(name);
+            assert!(self.is_registered(&id), "name not registered");
+            let name_data: DomainName = self.name_resource.get_non_fungible_data(&id);
+            let current_epoch = Runtime::current_epoch();
+            assert!(current_epoch <= name_data.last_valid_epoch, "name expired");
+
+            let ttl_epochs = name_data.ttl_epochs.unwrap_or(self.default_ttl_epochs);
+            let cache_until = current_epoch
+                .number()
+                .saturating_add(ttl_epochs)
+                .min(name_data.last_valid_epoch.number());
+            (name_data.address, cache_until)
+        }
+
+        /// Looks up the address of each of the given `names`.
+        /// Unlike `lookup_address`, this never panics: names that are malformed, not registered or
+        /// expired resolve to `None`.
+        ///
+        /// At most `MAX_BATCH_SIZE` names can be looked up at once.
+        pub fn lookup_addresses(
+            &self,
+            names: Vec<String>,
+        ) -> Vec<(String, Option<ComponentAddress>)> {
+            Self::assert_batch_size(&names);
+            let current_epoch = Runtime::current_epoch();
+            names
+                .into_iter()
+                .map(|name| {
+                    let id = Self::name_id(name.clone());
+                    let address = if Self::is_valid_name(&name) && self.is_registered(&id) {
+                        let name_data: DomainName = self.name_resource.get_non_fungible_data(&id);
+                        (current_epoch <= name_data.last_valid_epoch).then_some(name_data.address)
+                    } else {
+                        None
+                    };
+                    (name, address)
+                })
+                .collect()
+        }
+
+        /// Checks whether each of the given `names` can currently be registered.
+        /// Malformed names are reported as unavailable instead of causing a panic.
+        ///
+        /// Note that a name which has expired but has not been burned yet is still reported as
+        /// unavailable, since it stays registered until it is burned.
+        ///
+        /// At most `MAX_BATCH_SIZE` names can be checked at once.
+        pub fn check_availability(&self, names: Vec<String>) -> Vec<(String, bool)> {
+            Self::assert_batch_size(&names);
+            names
+                .into_iter()
+                .map(|name| {
+                    let available = Self::is_valid_name(&name)
+                        && !self.is_registered(&Self::name_id(name.clone()));
+                    (name, available)
+                })
+                .collect()
+        }
+
+        /// Returns all text records of the given `name`, ordered by key.
+        pub fn list_records(&self, name: String) -> Vec<(String, String)> {
+            self.text_records
+                .get(&Self::name_id(name))
+                .map(|records| {
+                    records
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        /// Checks whether the given `name_nft` proves ownership of the given `name` and whether that
+        /// name has not expired yet. This never panics, so dApps can use it to let users sign in
+        /// with their names.
+        pub fn verify_ownership(&self, name: String, name_nft: Proof) -> bool {
+            if name_nft.resource_address() != self.name_resource.address() {
+                return false;
+            }
+
+            let id = Self::name_id(name);
+            let name_nft = name_nft.skip_checking();
+            if !name_nft
+                .as_non_fungible()
+                .non_fungible_local_ids()
+                .contains(&id)
+            {
+                return false;
+            }
+
+            let name_data: DomainName = self.name_resource.get_non_fungible_data(&id);
+            Runtime::current_epoch() <= name_data.last_valid_epoch
+        }
+
         /// Registers the given `name` and maps it to the given `target_address` for `reserve_years`.
         /// The supplied `deposit` is locked until the name is unregistered.
         ///
+        /// The `registrant` is the account registering the name, whose owner must be present. The
+        /// registration cooldown, the whitelist and the limit of names per account apply to this
+        /// account, and it can claim the deposit once the name has expired and was burned.
+        ///
+        /// A name that was reserved in the presale can only be registered with its `reservation`
+        /// NFT, which is burned, until the reservation expires. The amount paid in the presale
+        /// counts towards the deposit.
+        ///
         /// This method returns an NFT that represents ownership of the registered name and any
         /// overpaid deposit.
         pub fn register_name(
@@ -121,53 +483,180 @@ mod radix_name_service {
             target_address: ComponentAddress,
             reserve_years: u8,
             mut deposit: Bucket,
+            registrant: ComponentAddress,
+            reservation: Option<Bucket>,
         ) -> (Bucket, Bucket) {
-            assert!(name.ends_with(".xrd"), "The domain name must end on '.xrd'");
+            self.assert_not_paused();
+            assert!(
+                Self::is_valid_name(&name),
+                "The domain name must end on '.xrd'"
+            );
             assert!(
                 reserve_years > 0,
                 "A name must be reserved for at least one year"
             );
             assert!(
-                deposit.resource_address() == XRD,
-                "The deposit must be made in XRD"
+                reserve_years <= MAX_RESERVATION_YEARS,
+                "reservation period too long: a name can be reserved for at most {} years",
+                MAX_RESERVATION_YEARS
+            );
+            assert!(
+                deposit.resource_address() == self.payment_token,
+                "The deposit must be made in {:?}",
+                self.payment_token
             );
 
-            let hash = Self::hash_name(name);
+            assert_account_owner(registrant);
+            self.assert_whitelisted(registrant);
+            self.count_registered_name(registrant);
+            self.start_registration_cooldown(registrant);
+
+            let id = Self::name_id(name);
+            let presale_credit = self.redeem_reservation(&id, reservation);
             let deposit_amount = self
                 .deposit_per_year
                 .checked_mul(Decimal::from(reserve_years))
                 .unwrap();
-            let last_valid_epoch =
-                Runtime::current_epoch().number() + EPOCHS_PER_YEAR * u64::from(reserve_years);
+            let last_valid_epoch = Self::epoch_after_years(Runtime::current_epoch(), reserve_years);
 
+            // The presale payment is already held in the deposits vault
+            let missing_deposit_amount = deposit_amount
+                .checked_sub(presale_credit)
+                .unwrap()
+                .max(Decimal::zero());
             assert!(
-                deposit.amount() >= deposit_amount,
-                "Insufficient deposit. You need to send a deposit of {} XRD",
-                deposit_amount
+                deposit.amount() >= missing_deposit_amount,
+                "Insufficient deposit. You need to send a deposit of {} {:?}",
+                missing_deposit_amount,
+                self.payment_token
             );
 
             let name_data = DomainName {
                 address: target_address,
-                last_valid_epoch: Epoch::of(last_valid_epoch),
+                last_valid_epoch,
                 deposit_amount,
+                registered_epoch: Runtime::current_epoch(),
+                registrant_account: registrant,
+                ttl_epochs: None,
             };
 
-            let name_nft = self.name_resource.mint_non_fungible(
-                &NonFungibleLocalId::Bytes(
-                    BytesNonFungibleLocalId::new(hash.to_be_bytes().to_vec()).unwrap(),
-                ),
-                name_data,
-            );
+            let name_nft = if self
+                .released_names
+                .as_non_fungible()
+                .contains_non_fungible(&id)
+            {
+                self.reissue_name(&id, name_data)
+            } else {
+                self.name_resource.mint_non_fungible(&id, name_data)
+            };
 
-            self.deposits.put(deposit.take(deposit_amount));
+            self.deposits.put(deposit.take(missing_deposit_amount));
+            if presale_credit > deposit_amount {
+                deposit.put(
+                    self.deposits
+                        .take(presale_credit.checked_sub(deposit_amount).unwrap()),
+                );
+            }
 
             (name_nft, deposit)
         }
 
+        /// Reserves the right to register the given `name` later, for the whole `payment`, which
+        /// must cover at least the presale price. The payment counts towards the deposit when the
+        /// name is registered with the returned reservation NFT before it expires, and is refunded
+        /// through `refund_reservation` otherwise. A name whose reservation expired can be reserved
+        /// again, even if the expired reservation was not refunded yet.
+        pub fn reserve_presale(&mut self, name: String, payment: Bucket) -> Bucket {
+            self.assert_not_paused();
+            let presale_price = self.presale_price.expect("The presale is not open");
+            assert!(
+                Self::is_valid_name(&name),
+                "The domain name must end on '.xrd'"
+            );
+            assert!(
+                payment.resource_address() == self.payment_token,
+                "The payment must be made in {:?}",
+                self.payment_token
+            );
+            assert!(
+                payment.amount() >= presale_price,
+                "Insufficient payment. A presale reservation costs {} {:?}",
+                presale_price,
+                self.payment_token
+            );
+
+            let id = Self::name_id(name.clone());
+            let name_hash = Self::name_hash(&id);
+            let reserved = self
+                .current_reservation(name_hash)
+                .map_or(false, |(_, reservation)| {
+                    Runtime::current_epoch() <= reservation.last_valid_epoch
+                });
+            assert!(
+                !self.is_registered(&id) && !reserved,
+                "This name is already taken"
+            );
+
+            let paid = payment.amount();
+            let last_valid_epoch = Epoch::of(
+                Runtime::current_epoch()
+                    .number()
+                    .saturating_add(self.presale_reservation_epochs),
+            );
+            self.deposits.put(payment);
+
+            let reservation = self
+                .reservation_resource
+                .mint_ruid_non_fungible(NameReservation {
+                    name,
+                    paid,
+                    last_valid_epoch,
+                });
+            self.presale_reservations.insert(
+                name_hash,
+                reservation.as_non_fungible().non_fungible_local_id(),
+            );
+
+            reservation
+        }
+
+        /// Refunds the amount paid for the given `reservation` once it has expired without being
+        /// redeemed. The supplied `reservation` is burned.
+        pub fn refund_reservation(&mut self, reservation: Bucket) -> Bucket {
+            assert!(
+                reservation.resource_address() == self.reservation_resource.address()
+                    && reservation.amount() == Decimal::one(),
+                "The supplied bucket does not contain a single name reservation"
+            );
+
+            let reservation_nft: NonFungible<NameReservation> =
+                reservation.as_non_fungible().non_fungible();
+            let reservation_data = reservation_nft.data();
+            assert!(
+                Runtime::current_epoch() > reservation_data.last_valid_epoch,
+                "The reservation has not expired yet"
+            );
+
+            // The name may have been reserved again since this reservation expired
+            let name_hash = Self::name_hash(&Self::name_id(reservation_data.name.clone()));
+            if self
+                .current_reservation(name_hash)
+                .map_or(false, |(id, _)| id == *reservation_nft.local_id())
+            {
+                self.presale_reservations.remove(&name_hash);
+            }
+            reservation.burn();
+
+            self.deposits.take(reservation_data.paid)
+        }
+
         /// Unregister the name(s) that is/are represented by the given `name_nft` bucket.
         /// Returns a bucket with the tokens that were initially deposited when the name(s) was/were
-        /// registered.
-        /// The supplied `name_nft` is burned.
+        /// registered, minus the early unregister penalty for names that were held for less than the
+        /// minimum holding period. Any unused renewal credit is refunded to the account that
+        /// deposited it, see `claim_expired_refund`.
+        /// The supplied `name_nft` is kept by this component and re-issued when the name is
+        /// registered again.
         pub fn unregister_name(&mut self, name_nft: Bucket) -> Bucket {
             assert!(
                 name_nft.resource_address() == self.name_resource.address(),
@@ -175,44 +664,84 @@ mod radix_name_service {
             );
             assert!(!name_nft.is_empty(), "The supplied bucket is empty");
 
-            let total_deposit_amount = Decimal::zero();
+            let mut total_deposit_amount = Decimal::zero();
+            let mut total_penalty_amount = Decimal::zero();
+            let mut name_hashes = Vec::new();
             for nft in name_nft.as_non_fungible().non_fungibles::<DomainName>() {
-                total_deposit_amount.checked_add(nft.data().deposit_amount);
+                assert!(
+                    !self.is_frozen(nft.local_id()),
+                    "This name is frozen pending a dispute"
+                );
+                name_hashes.push(Self::name_hash(nft.local_id()));
+                self.text_records.remove(nft.local_id());
+                self.release_renewal_credit(nft.local_id());
+                let name_data = nft.data();
+                self.uncount_registered_name(name_data.registrant_account);
+                total_deposit_amount = total_deposit_amount
+                    .checked_add(name_data.deposit_amount)
+                    .unwrap();
+
+                let held_epochs =
+                    Runtime::current_epoch().number() - name_data.registered_epoch.number();
+                if held_epochs < self.min_holding_epochs {
+                    let penalty_amount = name_data
+                        .deposit_amount
+                        .checked_mul(self.early_unregister_penalty_percent)
+                        .unwrap()
+                        .checked_div(100)
+                        .unwrap();
+                    total_penalty_amount =
+                        total_penalty_amount.checked_add(penalty_amount).unwrap();
+                }
+            }
+
+            self.released_names.put(name_nft);
+            for name_hash in name_hashes {
+                self.notify_watchers(name_hash);
             }
 
-            name_nft.burn();
+            let mut refund = self.deposits.take(total_deposit_amount);
+            self.fees.put(refund.take(total_penalty_amount));
 
-            self.deposits.take(total_deposit_amount)
+            refund
         }
 
         /// Updates the address for the name that is represented by the given `name_nft`.
         /// The fee is not added to the initial deposit and is not returned when the name is
-        /// unregistered.
+        /// unregistered. The fee is discounted if `holder_proof` proves that the caller holds
+        /// another unexpired name.
         /// Returns any overpaid fees.
         pub fn update_address(
             &mut self,
             name_nft: Proof,
             new_address: ComponentAddress,
             mut fee: Bucket,
+            holder_proof: Option<Proof>,
         ) -> Bucket {
+            self.assert_not_paused();
             assert!(
-                fee.resource_address() == XRD,
-                "The fee must be payed in XRD"
+                fee.resource_address() == self.payment_token,
+                "The fee must be payed in {:?}",
+                self.payment_token
             );
 
             let name_nft = name_nft.check(self.name_resource.address());
+            let name_id = name_nft.as_non_fungible().non_fungible_local_id();
 
-            let fee_amount = self.fee_address_update;
+            let fee_amount =
+                self.apply_loyalty_discount(self.fee_address_update, &name_id, holder_proof);
             assert!(
                 fee.amount() >= fee_amount,
-                "Insufficient fee amount. You need to send a fee of {} XRD",
-                fee_amount
+                "Insufficient fee amount. You need to send a fee of {} {:?}",
+                fee_amount,
+                self.payment_token
             );
 
             let resource_manager = self.name_resource;
 
             let non_fungible: NonFungible<DomainName> = name_nft.as_non_fungible().non_fungible();
             let id = non_fungible.local_id();
+            assert!(!self.is_frozen(id), "This name is frozen pending a dispute");
 
             let old_name_data = resource_manager.get_non_fungible_data::<DomainName>(&id);
 
@@ -235,50 +764,302 @@ mod radix_name_service {
 
         /// Renews the name identified by the given `name_nft` for `renew_years`.
         /// The fee is not added to the initial deposit and is not returned when the name is
-        /// unregistered.
+        /// unregistered. The fee is discounted if `holder_proof` proves that the caller holds
+        /// another unexpired name.
         /// Returns any overpaid fees.
-        pub fn renew_name(&mut self, name_nft: Proof, renew_years: u8, mut fee: Bucket) -> Bucket {
+        pub fn renew_name(
+            &mut self,
+            name_nft: Proof,
+            renew_years: u8,
+            mut fee: Bucket,
+            holder_proof: Option<Proof>,
+        ) -> Bucket {
+            self.assert_not_paused();
             assert!(
-                fee.resource_address() == XRD,
-                "The fee must be payed in XRD"
+                fee.resource_address() == self.payment_token,
+                "The fee must be payed in {:?}",
+                self.payment_token
             );
             assert!(
                 renew_years > 0,
                 "The name must be renewed for at least one year"
             );
+            assert!(
+                renew_years <= MAX_RESERVATION_YEARS,
+                "reservation period too long: a name can be renewed for at most {} years",
+                MAX_RESERVATION_YEARS
+            );
 
             let name_nft = name_nft.check(self.name_resource.address());
+            let name_id = name_nft.as_non_fungible().non_fungible_local_id();
 
-            let fee_amount = self.fee_renewal_per_year.checked_mul(renew_years).unwrap();
+            let fee_amount = self.apply_loyalty_discount(
+                self.fee_renewal_per_year.checked_mul(renew_years).unwrap(),
+                &name_id,
+                holder_proof,
+            );
             assert!(
                 fee.amount() >= fee_amount,
-                "Insufficient fee amount. You need to send a fee of {} XRD",
-                fee_amount
+                "Insufficient fee amount. You need to send a fee of {} {:?}",
+                fee_amount,
+                self.payment_token
             );
 
             let resource_manager = self.name_resource;
 
             let non_fungible: NonFungible<DomainName> = name_nft.as_non_fungible().non_fungible();
             let id = non_fungible.local_id();
+            assert!(!self.is_frozen(id), "This name is frozen pending a dispute");
 
             let name_data = resource_manager.get_non_fungible_data::<DomainName>(&id);
 
             let new_last_valid_epoch =
-                name_data.last_valid_epoch.number() + EPOCHS_PER_YEAR * u64::from(renew_years);
+                Self::epoch_after_years(name_data.last_valid_epoch, renew_years);
 
             resource_manager.update_non_fungible_data(
                 &id,
-                "name_data",
-                Epoch::of(new_last_valid_epoch),
+                "last_valid_epoch",
+                new_last_valid_epoch,
             );
             self.fees.put(fee.take(fee_amount));
 
             fee
         }
 
-        /// Burns all names that have expired. Must be called regularly.
-        pub fn burn_expired_names(&self) {
-            todo!("This can be implemented as soon as resources can be recalled from vaults")
+        /// Sets the text record with the given `key` of the name identified by the given `name_nft`
+        /// to `value`, overwriting any previous value. An empty `value` deletes the record. The `key`
+        /// can be at most `MAX_RECORD_KEY_LENGTH` bytes long.
+        pub fn set_text_record(&mut self, name_nft: Proof, key: String, value: String) {
+            self.assert_not_paused();
+            assert!(!key.is_empty(), "The record key must not be empty");
+            assert!(
+                key.len() <= MAX_RECORD_KEY_LENGTH,
+                "The record key can be at most {} bytes long",
+                MAX_RECORD_KEY_LENGTH
+            );
+            assert!(
+                value.len() <= self.max_value_length as usize,
+                "The record value can be at most {} bytes long",
+                self.max_value_length
+            );
+
+            let name_nft = name_nft.check(self.name_resource.address());
+            let non_fungible: NonFungible<DomainName> = name_nft.as_non_fungible().non_fungible();
+            let id = non_fungible.local_id();
+            assert!(!self.is_frozen(id), "This name is frozen pending a dispute");
+
+            let mut records = self
+                .text_records
+                .get(id)
+                .map(|records| records.clone())
+                .unwrap_or_default();
+            if value.is_empty() {
+                records.remove(&key);
+            } else {
+                records.insert(key, value);
+                assert!(
+                    records.len() <= self.max_records_per_name as usize,
+                    "A name can hold at most {} text records",
+                    self.max_records_per_name
+                );
+            }
+            self.text_records.insert(id.clone(), records);
+        }
+
+        /// Sets the number of epochs for which resolvers may cache the address of the name identified
+        /// by the given `name_nft`, overriding the component's default TTL.
+        pub fn set_ttl(&mut self, name_nft: Proof, ttl: u64) {
+            self.assert_not_paused();
+
+            let name_nft = name_nft.check(self.name_resource.address());
+            let non_fungible: NonFungible<DomainName> = name_nft.as_non_fungible().non_fungible();
+            let id = non_fungible.local_id();
+            assert!(!self.is_frozen(id), "This name is frozen pending a dispute");
+
+            self.name_resource
+                .update_non_fungible_data(id, "ttl_epochs", Some(ttl));
+        }
+
+        /// Adds the given `credit` to the renewal credit of the name identified by the given
+        /// `name_nft`. The credit is used to renew the name through `auto_renew`.
+        ///
+        /// The `depositor` is the account paying the credit, whose owner must be present. Any unused
+        /// credit is refunded to it when the name is unregistered or burned, or when another account
+        /// deposits credit for the name, and can then be claimed through `claim_expired_refund`.
+        pub fn deposit_renewal_credit(
+            &mut self,
+            name_nft: Proof,
+            credit: Bucket,
+            depositor: ComponentAddress,
+        ) {
+            assert!(
+                credit.resource_address() == self.payment_token,
+                "The renewal credit must be payed in {:?}",
+                self.payment_token
+            );
+            assert_account_owner(depositor);
+
+            let name_nft = name_nft.check(self.name_resource.address());
+            let non_fungible: NonFungible<DomainName> = name_nft.as_non_fungible().non_fungible();
+            let id = non_fungible.local_id();
+
+            // A name only holds the credit of a single depositor, e.g. its current holder
+            let previous_depositor = self
+                .renewal_credit_depositors
+                .get(id)
+                .map(|depositor| *depositor);
+            if previous_depositor != Some(depositor) {
+                self.release_renewal_credit(id);
+                self.renewal_credit_depositors.insert(id.clone(), depositor);
+            }
+
+            if self.renewal_credits.get(id).is_none() {
+                self.renewal_credits
+                    .insert(id.clone(), Vault::new(self.payment_token));
+            }
+            self.renewal_credits.get_mut(id).unwrap().put(credit);
+        }
+
+        /// Renews the given `name` for one year, paying the renewal fee from its renewal credit.
+        /// This can be called by anyone, e.g. a keeper, but only while the name is in the grace
+        /// period right after its expiry.
+        pub fn auto_renew(&mut self, name: String) {
+            self.assert_not_paused();
+
+            let id = Self::name_id(name.clone());
+            assert!(self.is_registered(&id), "name not registered");
+            assert!(
+                !self.is_frozen(&id),
+                "This name is frozen pending a dispute"
+            );
+
+            let name_data: DomainName = self.name_resource.get_non_fungible_data(&id);
+            let current_epoch = Runtime::current_epoch().number();
+            let last_valid_epoch = name_data.last_valid_epoch.number();
+            assert!(
+                current_epoch > last_valid_epoch
+                    && current_epoch <= last_valid_epoch.saturating_add(GRACE_PERIOD_EPOCHS),
+                "A name can only be renewed automatically within {} epochs after it expired",
+                GRACE_PERIOD_EPOCHS
+            );
+
+            let fee_amount = self.fee_renewal_per_year;
+            let mut credit = self
+                .renewal_credits
+                .get_mut(&id)
+                .expect("This name has no renewal credit");
+            assert!(
+                credit.amount() >= fee_amount,
+                "Insufficient renewal credit. The renewal fee is {} {:?}",
+                fee_amount,
+                self.payment_token
+            );
+            self.fees.put(credit.take(fee_amount));
+            drop(credit);
+
+            let new_last_valid_epoch = Self::epoch_after_years(name_data.last_valid_epoch, 1);
+            self.name_resource.update_non_fungible_data(
+                &id,
+                "last_valid_epoch",
+                new_last_valid_epoch,
+            );
+
+            Runtime::emit_event(AutoRenewedEvent {
+                name,
+                last_valid_epoch: new_last_valid_epoch,
+            });
+        }
+
+        /// Burns the given `names` if they have expired and their grace period has passed, which
+        /// makes them available again. Must be called regularly. Frozen names are skipped. The NFTs
+        /// of burned names are kept by this component and re-issued when the names are registered
+        /// again.
+        ///
+        /// Each name is given with the `vault` that holds its NFT, from which it is recalled. A
+        /// component can't look up the vaults of an account, so they have to be looked up off-ledger
+        /// (e.g. through the Gateway). The deposit of each burned name is moved to the refunds of the
+        /// account that registered it, and any unused renewal credit to the refunds of the account
+        /// that deposited it, where they can be claimed through `claim_expired_refund`.
+        ///
+        /// At most `MAX_BATCH_SIZE` names can be passed at once, of which at most `limit` names are
+        /// burned if one is given. Returns which names were burned.
+        pub fn burn_expired_names(
+            &mut self,
+            names: Vec<(String, InternalAddress)>,
+            limit: Option<u64>,
+        ) -> CleanupReport {
+            Self::assert_batch_size(&names);
+            let current_epoch = Runtime::current_epoch().number();
+            let mut report = CleanupReport {
+                burned_count: 0,
+                names: Vec::new(),
+            };
+            for (name, vault) in names {
+                if limit.map_or(false, |limit| report.burned_count >= limit) {
+                    break;
+                }
+
+                let id = Self::name_id(name);
+                if !self.is_registered(&id) || self.is_frozen(&id) {
+                    continue;
+                }
+
+                let name_data: DomainName = self.name_resource.get_non_fungible_data(&id);
+                let grace_period_end = name_data
+                    .last_valid_epoch
+                    .number()
+                    .saturating_add(GRACE_PERIOD_EPOCHS);
+                if current_epoch <= grace_period_end {
+                    continue;
+                }
+
+                // Recall is a direct access method of the vault, which is owned by the name's
+                // holder rather than by this component, so it has to go through the engine.
+                let name_nft: Bucket = scrypto_decode(
+                    &ScryptoEnv
+                        .call_direct_access_method(
+                            vault.as_node_id(),
+                            NON_FUNGIBLE_VAULT_RECALL_NON_FUNGIBLES_IDENT,
+                            scrypto_args!(btreeset!(id.clone())),
+                        )
+                        .unwrap(),
+                )
+                .unwrap();
+                self.released_names.put(name_nft);
+
+                let deposit = self.deposits.take(name_data.deposit_amount);
+                self.refund_to(name_data.registrant_account, deposit);
+                self.release_renewal_credit(&id);
+                self.text_records.remove(&id);
+                self.uncount_registered_name(name_data.registrant_account);
+
+                self.notify_watchers(Self::name_hash(&id));
+                report.burned_count += 1;
+                report.names.push(id);
+            }
+
+            report
+        }
+
+        /// Returns the number of names registered by the given `account` that were neither
+        /// unregistered nor burned after expiring. Names count against their registrant even if
+        /// they are transferred to another account.
+        pub fn names_held_count(&self, account: ComponentAddress) -> u32 {
+            self.names_held
+                .get(&account)
+                .map_or(0, |names_held| *names_held)
+        }
+
+        /// Returns the deposits of all expired names that were registered by the given `account` and
+        /// burned since its last claim, along with the unused renewal credits it deposited that were
+        /// refunded since. The owner of the account must be present.
+        pub fn claim_expired_refund(&mut self, account: ComponentAddress) -> Bucket {
+            assert_account_owner(account);
+
+            self.expired_refunds
+                .get_mut(&account)
+                .expect("This account has no expired refund")
+                .take_all()
         }
 
         /// Withdraws all fees that have been paid to this component. This does not
@@ -287,6 +1068,375 @@ mod radix_name_service {
             self.fees.take_all()
         }
 
+        /// Withdraws all fees that have been paid to this component and deposits them directly
+        /// into the given `account`.
+        pub fn withdraw_fees_to(&mut self, account: ComponentAddress) {
+            let mut account: Global<Account> = Global::from(account);
+            account.try_deposit_or_abort(self.fees.take_all(), None);
+        }
+
+        /// Returns the amount of fees that are currently available for withdrawal.
+        pub fn available_fees(&self) -> Decimal {
+            self.fees.amount()
+        }
+
+        /// Registers the `watcher` to be notified through a `WatchedNameAvailableEvent` when the
+        /// currently registered `name` becomes available again. Requires the owner of the `watcher`
+        /// account, so that nobody can fill the watchers of a name with accounts of their own.
+        pub fn watch_name(&mut self, name: String, watcher: ComponentAddress) {
+            assert_account_owner(watcher);
+            assert!(
+                self.is_registered(&Self::name_id(name.clone())),
+                "Only registered names can be watched"
+            );
+
+            let name_hash = Self::hash_name(name);
+            let mut watchers = self
+                .watchers
+                .get(&name_hash)
+                .map(|watchers| watchers.clone())
+                .unwrap_or_default();
+            if watchers.contains(&watcher) {
+                return;
+            }
+            assert!(
+                watchers.len() < MAX_WATCHERS_PER_NAME,
+                "This name already has the maximum of {} watchers",
+                MAX_WATCHERS_PER_NAME
+            );
+            watchers.push(watcher);
+            self.watchers.insert(name_hash, watchers);
+        }
+
+        /// Emits a `WatchedNameAvailableEvent` for the name with the given hash if anybody is
+        /// watching it. Must be called whenever a name becomes available again.
+        fn notify_watchers(&mut self, name_hash: u128) {
+            if let Some(watchers) = self.watchers.remove(&name_hash) {
+                Runtime::emit_event(WatchedNameAvailableEvent {
+                    name_hash,
+                    watchers,
+                });
+            }
+        }
+
+        /// Returns the given `fee_amount` minus the loyalty discount if the given `holder_proof`
+        /// contains an unexpired name NFT other than the one with the given `name_id` that the fee is
+        /// paid for.
+        fn apply_loyalty_discount(
+            &self,
+            fee_amount: Decimal,
+            name_id: &NonFungibleLocalId,
+            holder_proof: Option<Proof>,
+        ) -> Decimal {
+            let holder_proof = match holder_proof {
+                Some(holder_proof) => holder_proof.check(self.name_resource.address()),
+                None => return fee_amount,
+            };
+
+            let current_epoch = Runtime::current_epoch();
+            let holds_unexpired_name = holder_proof
+                .as_non_fungible()
+                .non_fungibles::<DomainName>()
+                .iter()
+                .any(|nft| {
+                    nft.local_id() != name_id && current_epoch <= nft.data().last_valid_epoch
+                });
+            if !holds_unexpired_name {
+                return fee_amount;
+            }
+
+            let discount_amount = fee_amount
+                .checked_mul(self.loyalty_discount_percent)
+                .unwrap()
+                .checked_div(100)
+                .unwrap();
+            fee_amount.checked_sub(discount_amount).unwrap()
+        }
+
+        /// Re-issues the NFT of the released name with the given `id`, replacing its data with the
+        /// given `name_data`.
+        fn reissue_name(&mut self, id: &NonFungibleLocalId, name_data: DomainName) -> Bucket {
+            let resource_manager = self.name_resource;
+            resource_manager.update_non_fungible_data(id, "address", name_data.address);
+            resource_manager.update_non_fungible_data(
+                id,
+                "last_valid_epoch",
+                name_data.last_valid_epoch,
+            );
+            resource_manager.update_non_fungible_data(
+                id,
+                "deposit_amount",
+                name_data.deposit_amount,
+            );
+            resource_manager.update_non_fungible_data(
+                id,
+                "registered_epoch",
+                name_data.registered_epoch,
+            );
+            resource_manager.update_non_fungible_data(
+                id,
+                "registrant_account",
+                name_data.registrant_account,
+            );
+            resource_manager.update_non_fungible_data(id, "ttl_epochs", name_data.ttl_epochs);
+
+            self.released_names
+                .as_non_fungible()
+                .take_non_fungible(id)
+                .into()
+        }
+
+        /// Adds the given `refund` to the refunds of the given `account`, which it can claim through
+        /// `claim_expired_refund`.
+        fn refund_to(&mut self, account: ComponentAddress, refund: Bucket) {
+            if self.expired_refunds.get(&account).is_none() {
+                self.expired_refunds
+                    .insert(account, Vault::new(self.payment_token));
+            }
+            self.expired_refunds.get_mut(&account).unwrap().put(refund);
+        }
+
+        /// Refunds the unused renewal credit of the name with the given `id` to the account that
+        /// deposited it.
+        fn release_renewal_credit(&mut self, id: &NonFungibleLocalId) {
+            if let Some(depositor) = self.renewal_credit_depositors.remove(id) {
+                let credit = self.renewal_credits.get_mut(id).unwrap().take_all();
+                self.refund_to(depositor, credit);
+            }
+        }
+
+        /// Counts a name registered by the given `registrant_account`, panicking if that would exceed
+        /// the limit of names per account.
+        fn count_registered_name(&mut self, registrant_account: ComponentAddress) {
+            let names_held = self.names_held_count(registrant_account);
+            assert!(
+                self.max_names_per_account == 0 || names_held < self.max_names_per_account,
+                "name limit reached: an account can hold at most {} names",
+                self.max_names_per_account
+            );
+            self.names_held.insert(registrant_account, names_held + 1);
+        }
+
+        /// Releases a name that was counted for the given `registrant_account`.
+        fn uncount_registered_name(&mut self, registrant_account: ComponentAddress) {
+            let names_held = self.names_held_count(registrant_account);
+            self.names_held
+                .insert(registrant_account, names_held.saturating_sub(1));
+        }
+
+        /// Burns the given `reservation` for the name with the given `id` and returns the amount paid
+        /// for it in the presale. Panics if the name is reserved but the matching reservation is
+        /// missing, or if a reservation other than the current one for the name or an expired
+        /// reservation is given.
+        fn redeem_reservation(
+            &mut self,
+            id: &NonFungibleLocalId,
+            reservation: Option<Bucket>,
+        ) -> Decimal {
+            let name_hash = Self::name_hash(id);
+            let reserved = self.current_reservation(name_hash);
+            let current_epoch = Runtime::current_epoch();
+            match (reserved, reservation) {
+                (None, None) => Decimal::zero(),
+                // Once its reservation expired, anybody can register the name
+                (Some((_, reserved)), None) if current_epoch > reserved.last_valid_epoch => {
+                    self.presale_reservations.remove(&name_hash);
+                    Decimal::zero()
+                }
+                (Some(_), None) => panic!("This name is reserved in the presale"),
+                (reserved, Some(reservation)) => {
+                    assert!(
+                        reservation.resource_address() == self.reservation_resource.address()
+                            && reservation.amount() == Decimal::one(),
+                        "The supplied bucket does not contain a single name reservation"
+                    );
+                    let (_, reserved) = reserved
+                        .filter(|(reservation_id, _)| {
+                            reservation.as_non_fungible().non_fungible_local_id() == *reservation_id
+                        })
+                        .expect("The reservation is not for this name");
+                    assert!(
+                        current_epoch <= reserved.last_valid_epoch,
+                        "The reservation has expired, refund it instead"
+                    );
+                    reservation.burn();
+                    self.presale_reservations.remove(&name_hash);
+                    reserved.paid
+                }
+            }
+        }
+
+        /// Returns the ID and data of the current reservation of the name with the given
+        /// `name_hash`, if it is reserved in the presale.
+        fn current_reservation(
+            &self,
+            name_hash: u128,
+        ) -> Option<(NonFungibleLocalId, NameReservation)> {
+            let id = self.presale_reservations.get(&name_hash)?.clone();
+            let reservation = self.reservation_resource.get_non_fungible_data(&id);
+            Some((id, reservation))
+        }
+
+        /// Panics if registration is restricted to the whitelist and the given `registrant` is not
+        /// a whitelisted account.
+        fn assert_whitelisted(&self, registrant: ComponentAddress) {
+            if !self.whitelist_only {
+                return;
+            }
+
+            assert!(
+                self.whitelist.get(&registrant).is_some(),
+                "registration restricted to whitelist"
+            );
+        }
+
+        /// Records a registration by the given `registrant` account, panicking if that registrant is
+        /// still in the cooldown of their previous registration.
+        fn start_registration_cooldown(&mut self, registrant: ComponentAddress) {
+            let current_epoch = Runtime::current_epoch();
+            if let Some(last_registration) = self.last_registrations.get(&registrant) {
+                let cooldown_end = last_registration
+                    .number()
+                    .saturating_add(self.registration_cooldown_epochs);
+                assert!(
+                    current_epoch.number() >= cooldown_end,
+                    "registration cooldown: wait {} more epochs before registering another name",
+                    cooldown_end - current_epoch.number()
+                );
+            }
+            self.last_registrations.insert(registrant, current_epoch);
+        }
+
+        /// Panics if more than `MAX_BATCH_SIZE` names are passed to a batch method.
+        fn assert_batch_size<T>(names: &[T]) {
+            assert!(
+                names.len() <= MAX_BATCH_SIZE,
+                "batch too large: at most {} names can be processed at once",
+                MAX_BATCH_SIZE
+            );
+        }
+
+        /// Returns the epoch that lies `years` years after the given `epoch`.
+        /// Panics instead of overflowing if that epoch can not be represented.
+        fn epoch_after_years(epoch: Epoch, years: u8) -> Epoch {
+            EPOCHS_PER_YEAR
+                .checked_mul(u64::from(years))
+                .and_then(|epochs| epoch.number().checked_add(epochs))
+                .map(Epoch::of)
+                .expect("reservation period too long")
+        }
+
+        /// Checks that the given `name` is a label followed by the '.xrd' top-level domain.
+        fn is_valid_name(name: &str) -> bool {
+            name.len() > ".xrd".len() && name.ends_with(".xrd")
+        }
+
+        /// Returns the ID of the NFT that represents the given `name`.
+        fn name_id(name: String) -> NonFungibleLocalId {
+            let hash = Self::hash_name(name);
+            NonFungibleLocalId::Bytes(
+                BytesNonFungibleLocalId::new(hash.to_be_bytes().to_vec()).unwrap(),
+            )
+        }
+
+        /// Returns the hash of the name that is represented by the NFT with the given `id`.
+        fn name_hash(id: &NonFungibleLocalId) -> u128 {
+            match id {
+                NonFungibleLocalId::Bytes(bytes) => {
+                    u128::from_be_bytes(bytes.value().try_into().unwrap())
+                }
+                _ => panic!("Domain name NFTs have bytes IDs"),
+            }
+        }
+
+        /// Freezes the given `name` pending a dispute. While frozen, the name can still be looked up
+        /// but its address can not be updated and it can not be renewed, unregistered or burned, so
+        /// the freeze lasts until it is lifted through `unfreeze_name`. Only registered names can be
+        /// frozen.
+        pub fn freeze_name(&mut self, name: String) {
+            let id = Self::name_id(name.clone());
+            assert!(
+                self.is_registered(&id),
+                "Only registered names can be frozen"
+            );
+            self.frozen_names.insert(id, true);
+            Runtime::emit_event(NameFrozenEvent { name });
+        }
+
+        /// Lifts a freeze that was previously placed on the given `name`.
+        pub fn unfreeze_name(&mut self, name: String) {
+            self.frozen_names.remove(&Self::name_id(name.clone()));
+            Runtime::emit_event(NameUnfrozenEvent { name });
+        }
+
+        /// Pauses or resumes the service. While paused, names can not be registered, updated or
+        /// renewed, but they can still be looked up and unregistered so that users can always get
+        /// their deposits back.
+        pub fn set_paused(&mut self, paused: bool) {
+            self.paused = paused;
+            Runtime::emit_event(PausedEvent { paused });
+        }
+
+        /// Sets the maximum number of names an account can hold. A limit of zero disables this.
+        /// Accounts that already hold more names keep them, but can't register any more.
+        pub fn set_max_names_per_account(&mut self, max_names_per_account: u32) {
+            self.max_names_per_account = max_names_per_account;
+        }
+
+        /// Opens the presale, in which names can be reserved for at least `price` each. Each
+        /// reservation can be redeemed for `reservation_epochs` epochs, after which the name is free
+        /// again.
+        pub fn open_presale(&mut self, price: Decimal, reservation_epochs: u64) {
+            assert!(
+                price >= Decimal::zero(),
+                "The presale price can't be negative"
+            );
+            self.presale_price = Some(price);
+            self.presale_reservation_epochs = reservation_epochs;
+        }
+
+        /// Closes the presale. Names that were already reserved stay reserved.
+        pub fn close_presale(&mut self) {
+            self.presale_price = None;
+        }
+
+        /// Restricts registration to whitelisted accounts, or opens it up to everybody again.
+        pub fn set_whitelist_only(&mut self, whitelist_only: bool) {
+            self.whitelist_only = whitelist_only;
+        }
+
+        /// Allows the given `account` to register names while registration is restricted to the
+        /// whitelist.
+        pub fn add_to_whitelist(&mut self, account: ComponentAddress) {
+            self.whitelist.insert(account, ());
+        }
+
+        /// Removes the given `account` from the whitelist.
+        pub fn remove_from_whitelist(&mut self, account: ComponentAddress) {
+            self.whitelist.remove(&account);
+        }
+
+        fn assert_not_paused(&self) {
+            assert!(!self.paused, "service paused");
+        }
+
+        /// Checks whether the name with the given `id` is registered, i.e. whether its NFT exists and
+        /// has not been released.
+        fn is_registered(&self, id: &NonFungibleLocalId) -> bool {
+            self.name_resource.non_fungible_exists(id)
+                && !self
+                    .released_names
+                    .as_non_fungible()
+                    .contains_non_fungible(id)
+        }
+
+        fn is_frozen(&self, id: &NonFungibleLocalId) -> bool {
+            self.frozen_names
+                .get(id)
+                .map(|frozen| *frozen)
+                .unwrap_or(false)
+        }
+
         /// Calculates a hash for the given `name`.
         ///
         /// The hash is calculated by applying SHA256 to the given name
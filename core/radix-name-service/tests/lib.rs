@@ -0,0 +1,2001 @@
+use radix_engine::transaction::TransactionReceipt;
+use radix_name_service::CleanupReport;
+use scrypto::prelude::*;
+use scrypto_unit::*;
+use sha2::{Digest, Sha256};
+use transaction::{builder::ManifestBuilder, prelude::TransactionManifestV1};
+
+pub struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    public_key: Secp256k1PublicKey,
+    account_address: ComponentAddress,
+    component_address: ComponentAddress,
+    admin_badge: ResourceAddress,
+    name_resource: ResourceAddress,
+    payment_token: ResourceAddress,
+    reservation_resource: ResourceAddress,
+}
+
+/// The optional features of the name service, mirroring the `RnsConfig` of the blueprint.
+#[derive(ManifestSbor)]
+pub struct RnsConfig {
+    payment_token: Option<ResourceAddress>,
+    early_unregister_penalty_percent: Decimal,
+    min_holding_epochs: u64,
+    registration_cooldown_epochs: u64,
+    loyalty_discount_percent: Decimal,
+    max_records_per_name: u32,
+    max_value_length: u32,
+    whitelist_only: bool,
+    max_names_per_account: u32,
+    default_ttl_epochs: u64,
+}
+
+/// The arguments used to instantiate the name service, other than its fees.
+pub struct RnsArguments {
+    /// Pays with a newly created token held by the test account instead of XRD.
+    custom_payment_token: bool,
+    early_unregister_penalty_percent: Decimal,
+    min_holding_epochs: u64,
+    registration_cooldown_epochs: u64,
+    loyalty_discount_percent: Decimal,
+    max_records_per_name: u32,
+    max_value_length: u32,
+    whitelist_only: bool,
+    max_names_per_account: u32,
+    default_ttl_epochs: u64,
+}
+
+impl Default for RnsArguments {
+    fn default() -> Self {
+        Self {
+            custom_payment_token: false,
+            early_unregister_penalty_percent: dec!(0),
+            min_holding_epochs: 0,
+            registration_cooldown_epochs: 0,
+            loyalty_discount_percent: dec!(0),
+            max_records_per_name: 10,
+            max_value_length: 100,
+            whitelist_only: false,
+            max_names_per_account: 0,
+            default_ttl_epochs: 100,
+        }
+    }
+}
+
+impl TestEnvironment {
+    pub fn instantiate_test() -> Self {
+        Self::instantiate_test_with(RnsArguments::default())
+    }
+
+    /// Instantiates the name service with the given arguments.
+    pub fn instantiate_test_with(arguments: RnsArguments) -> Self {
+        let mut test_runner = TestRunnerBuilder::new().build();
+
+        // Create an account, which holds the custom payment token if one is used
+        let (public_key, _private_key, account_address) = test_runner.new_virtual_account();
+        let payment_token = arguments
+            .custom_payment_token
+            .then(|| test_runner.create_fungible_resource(dec!(1000), 18, account_address));
+
+        // Publish package and instantiate the name service
+        let package_address = test_runner.compile_and_publish(this_package!());
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                package_address,
+                "RadixNameService",
+                "instantiate_rns",
+                manifest_args!(
+                    dec!(10),
+                    dec!(1),
+                    dec!(2),
+                    RnsConfig {
+                        payment_token,
+                        early_unregister_penalty_percent: arguments
+                            .early_unregister_penalty_percent,
+                        min_holding_epochs: arguments.min_holding_epochs,
+                        registration_cooldown_epochs: arguments.registration_cooldown_epochs,
+                        loyalty_discount_percent: arguments.loyalty_discount_percent,
+                        max_records_per_name: arguments.max_records_per_name,
+                        max_value_length: arguments.max_value_length,
+                        whitelist_only: arguments.whitelist_only,
+                        max_names_per_account: arguments.max_names_per_account,
+                        default_ttl_epochs: arguments.default_ttl_epochs,
+                    }
+                ),
+            )
+            .deposit_batch(account_address)
+            .build();
+        let receipt = test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        );
+        let commit = receipt.expect_commit_success();
+        let component_address = commit.new_component_addresses()[0];
+        let admin_badge = commit.new_resource_addresses()[0];
+        let name_resource = commit.new_resource_addresses()[1];
+        let reservation_resource = commit.new_resource_addresses()[2];
+
+        Self {
+            test_runner,
+            public_key,
+            account_address,
+            component_address,
+            admin_badge,
+            name_resource,
+            payment_token: payment_token.unwrap_or(XRD),
+            reservation_resource,
+        }
+    }
+
+    pub fn execute(&mut self, manifest: TransactionManifestV1) -> TransactionReceipt {
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    pub fn register_name(
+        &mut self,
+        name: &str,
+        reserve_years: u8,
+        deposit: Decimal,
+    ) -> TransactionReceipt {
+        let registrant = self.account_address;
+        self.register_name_for(registrant, name, reserve_years, deposit)
+    }
+
+    /// Registers a name from the test account on behalf of the given registrant account.
+    pub fn register_name_for(
+        &mut self,
+        registrant: ComponentAddress,
+        name: &str,
+        reserve_years: u8,
+        deposit: Decimal,
+    ) -> TransactionReceipt {
+        let (public_key, account_address) = (self.public_key, self.account_address);
+        self.register_name_as(
+            public_key,
+            account_address,
+            registrant,
+            name,
+            reserve_years,
+            deposit,
+        )
+    }
+
+    /// Registers a name from the given account, signing with the given key, on behalf of the
+    /// given registrant account.
+    pub fn register_name_as(
+        &mut self,
+        public_key: Secp256k1PublicKey,
+        account_address: ComponentAddress,
+        registrant: ComponentAddress,
+        name: &str,
+        reserve_years: u8,
+        deposit: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(account_address, self.payment_token, deposit)
+            .take_all_from_worktop(self.payment_token, "deposit")
+            .call_method_with_name_lookup(self.component_address, "register_name", |lookup| {
+                (
+                    name.to_string(),
+                    account_address,
+                    reserve_years,
+                    lookup.bucket("deposit"),
+                    registrant,
+                    None::<ManifestBucket>,
+                )
+            })
+            .deposit_batch(account_address)
+            .build();
+
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+    }
+
+    /// Reserves `name` in the presale, paying `payment`.
+    pub fn reserve_presale(&mut self, name: &str, payment: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account_address, self.payment_token, payment)
+            .take_all_from_worktop(self.payment_token, "payment")
+            .call_method_with_name_lookup(self.component_address, "reserve_presale", |lookup| {
+                (name.to_string(), lookup.bucket("payment"))
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Refunds the single presale reservation held by the test account.
+    pub fn refund_reservation(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account_address, self.reservation_resource, dec!(1))
+            .take_all_from_worktop(self.reservation_resource, "reservation")
+            .call_method_with_name_lookup(self.component_address, "refund_reservation", |lookup| {
+                (lookup.bucket("reservation"),)
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Registers `name` with the single presale reservation held by the test account.
+    pub fn register_reserved_name(
+        &mut self,
+        name: &str,
+        reserve_years: u8,
+        deposit: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account_address, self.payment_token, deposit)
+            .take_all_from_worktop(self.payment_token, "deposit")
+            .withdraw_from_account(self.account_address, self.reservation_resource, dec!(1))
+            .take_all_from_worktop(self.reservation_resource, "reservation")
+            .call_method_with_name_lookup(self.component_address, "register_name", |lookup| {
+                (
+                    name.to_string(),
+                    self.account_address,
+                    reserve_years,
+                    lookup.bucket("deposit"),
+                    self.account_address,
+                    Some(lookup.bucket("reservation")),
+                )
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Unregisters the single name held by the test account.
+    pub fn unregister_name(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account_address, self.name_resource, dec!(1))
+            .take_all_from_worktop(self.name_resource, "name_nft")
+            .call_method_with_name_lookup(self.component_address, "unregister_name", |lookup| {
+                (lookup.bucket("name_nft"),)
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Checks whether the single given `name` is available.
+    pub fn check_availability(&mut self, name: &str) -> (String, bool) {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "check_availability",
+                manifest_args!(vec![name.to_string()]),
+            )
+            .build();
+
+        let availability: Vec<(String, bool)> =
+            self.execute(manifest).expect_commit_success().output(1);
+        availability.into_iter().next().unwrap()
+    }
+
+    /// Verifies ownership of `name` with a proof of the single name held by the test account.
+    pub fn verify_ownership(&mut self, name: &str) -> bool {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.name_resource, dec!(1))
+            .pop_from_auth_zone("name_nft")
+            .call_method_with_name_lookup(self.component_address, "verify_ownership", |lookup| {
+                (name.to_string(), lookup.proof("name_nft"))
+            })
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(3)
+    }
+
+    pub fn lookup_address(&mut self, name: &str) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "lookup_address",
+                manifest_args!(name.to_string()),
+            )
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Updates the address of the single name held by the test account.
+    pub fn update_address(
+        &mut self,
+        new_address: ComponentAddress,
+        fee: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.name_resource, dec!(1))
+            .pop_from_auth_zone("name_nft")
+            .withdraw_from_account(self.account_address, self.payment_token, fee)
+            .take_all_from_worktop(self.payment_token, "fee")
+            .call_method_with_name_lookup(self.component_address, "update_address", |lookup| {
+                (
+                    lookup.proof("name_nft"),
+                    new_address,
+                    lookup.bucket("fee"),
+                    None::<ManifestProof>,
+                )
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Renews the single name held by the test account.
+    pub fn renew_name(&mut self, renew_years: u8, fee: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.name_resource, dec!(1))
+            .pop_from_auth_zone("name_nft")
+            .withdraw_from_account(self.account_address, self.payment_token, fee)
+            .take_all_from_worktop(self.payment_token, "fee")
+            .call_method_with_name_lookup(self.component_address, "renew_name", |lookup| {
+                (
+                    lookup.proof("name_nft"),
+                    renew_years,
+                    lookup.bucket("fee"),
+                    None::<ManifestProof>,
+                )
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Renews `name`, proving that the test account also holds `holder_name`.
+    pub fn renew_name_with_holder_proof(
+        &mut self,
+        name: &str,
+        holder_name: &str,
+        renew_years: u8,
+        fee: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account_address,
+                self.name_resource,
+                btreeset!(name_id(name)),
+            )
+            .pop_from_auth_zone("name_nft")
+            .create_proof_from_account_of_non_fungibles(
+                self.account_address,
+                self.name_resource,
+                btreeset!(name_id(holder_name)),
+            )
+            .pop_from_auth_zone("holder_proof")
+            .withdraw_from_account(self.account_address, self.payment_token, fee)
+            .take_all_from_worktop(self.payment_token, "fee")
+            .call_method_with_name_lookup(self.component_address, "renew_name", |lookup| {
+                (
+                    lookup.proof("name_nft"),
+                    renew_years,
+                    lookup.bucket("fee"),
+                    Some(lookup.proof("holder_proof")),
+                )
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Deposits renewal credit for the single name held by the test account.
+    pub fn deposit_renewal_credit(&mut self, credit: Decimal) -> TransactionReceipt {
+        let (public_key, account_address) = (self.public_key, self.account_address);
+        self.deposit_renewal_credit_as(public_key, account_address, credit)
+    }
+
+    /// Deposits renewal credit from the given account, signing with the given key, for the single
+    /// name held by that account.
+    pub fn deposit_renewal_credit_as(
+        &mut self,
+        public_key: Secp256k1PublicKey,
+        account_address: ComponentAddress,
+        credit: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(account_address, self.name_resource, dec!(1))
+            .pop_from_auth_zone("name_nft")
+            .withdraw_from_account(account_address, self.payment_token, credit)
+            .take_all_from_worktop(self.payment_token, "credit")
+            .call_method_with_name_lookup(
+                self.component_address,
+                "deposit_renewal_credit",
+                |lookup| {
+                    (
+                        lookup.proof("name_nft"),
+                        lookup.bucket("credit"),
+                        account_address,
+                    )
+                },
+            )
+            .build();
+
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+    }
+
+    pub fn auto_renew(&mut self, name: &str) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "auto_renew",
+                manifest_args!(name.to_string()),
+            )
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn advance_epochs(&mut self, epochs: u64) {
+        let current_epoch = self.test_runner.get_current_epoch();
+        self.test_runner
+            .set_current_epoch(current_epoch.after(epochs).unwrap());
+    }
+
+    /// Sets a text record of the single name held by the test account.
+    pub fn set_text_record(&mut self, key: &str, value: &str) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.name_resource, dec!(1))
+            .pop_from_auth_zone("name_nft")
+            .call_method_with_name_lookup(self.component_address, "set_text_record", |lookup| {
+                (lookup.proof("name_nft"), key.to_string(), value.to_string())
+            })
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Sets the TTL of the single name held by the test account.
+    pub fn set_ttl(&mut self, ttl: u64) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.name_resource, dec!(1))
+            .pop_from_auth_zone("name_nft")
+            .call_method_with_name_lookup(self.component_address, "set_ttl", |lookup| {
+                (lookup.proof("name_nft"), ttl)
+            })
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Returns the address of `name` and the epoch until which it may be cached.
+    pub fn lookup_with_ttl(&mut self, name: &str) -> (ComponentAddress, u64) {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "lookup_with_ttl",
+                manifest_args!(name.to_string()),
+            )
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+
+    pub fn list_records(&mut self, name: &str) -> Vec<(String, String)> {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "list_records",
+                manifest_args!(name.to_string()),
+            )
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+
+    /// Burns the expired `name` as the admin, recalling its NFT from the given `holder` account.
+    pub fn burn_expired_name(
+        &mut self,
+        name: &str,
+        holder: ComponentAddress,
+    ) -> TransactionReceipt {
+        self.burn_expired_names(&[name], holder, None)
+    }
+
+    /// Burns at most `limit` of the expired `names` as the admin, recalling their NFTs from the
+    /// given `holder` account.
+    pub fn burn_expired_names(
+        &mut self,
+        names: &[&str],
+        holder: ComponentAddress,
+        limit: Option<u64>,
+    ) -> TransactionReceipt {
+        let vault = InternalAddress::new_or_panic(
+            self.test_runner
+                .get_component_vaults(holder, self.name_resource)[0]
+                .0,
+        );
+        let names: Vec<(String, InternalAddress)> =
+            names.iter().map(|name| (name.to_string(), vault)).collect();
+        self.call_as_admin("burn_expired_names", manifest_args!(names, limit))
+    }
+
+    /// Registers the `watcher` account, signing with its `public_key`, to be notified when `name`
+    /// becomes available again.
+    pub fn watch_name(
+        &mut self,
+        name: &str,
+        public_key: Secp256k1PublicKey,
+        watcher: ComponentAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "watch_name",
+                manifest_args!(name.to_string(), watcher),
+            )
+            .build();
+
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+    }
+
+    /// Returns the hash and the watchers of every `WatchedNameAvailableEvent` of the given receipt.
+    pub fn watched_name_notifications(
+        &self,
+        receipt: &TransactionReceipt,
+    ) -> Vec<(u128, Vec<ComponentAddress>)> {
+        receipt
+            .expect_commit_success()
+            .application_events
+            .iter()
+            .filter(|(event_type_identifier, _)| {
+                self.test_runner.event_name(event_type_identifier) == "WatchedNameAvailableEvent"
+            })
+            .map(|(_, event_data)| scrypto_decode(event_data).unwrap())
+            .collect()
+    }
+
+    /// Claims the expired refund of the given account, signing as the owner of the test account.
+    pub fn claim_expired_refund(&mut self, account: ComponentAddress) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "claim_expired_refund",
+                manifest_args!(account),
+            )
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Claims the expired refund of the given account into it, signing with the given key.
+    pub fn claim_expired_refund_as(
+        &mut self,
+        public_key: Secp256k1PublicKey,
+        account: ComponentAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "claim_expired_refund",
+                manifest_args!(account),
+            )
+            .deposit_batch(account)
+            .build();
+
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+    }
+
+    pub fn names_held_count(&mut self, account: ComponentAddress) -> u32 {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "names_held_count",
+                manifest_args!(account),
+            )
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+
+    /// Calls an admin-gated method with a proof of the admin badge.
+    pub fn call_as_admin(&mut self, method_name: &str, args: ManifestArgs) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.admin_badge, dec!(1))
+            .call_method(self.component_address, method_name, args)
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn available_fees(&mut self) -> Decimal {
+        let manifest = ManifestBuilder::new()
+            .call_method(self.component_address, "available_fees", manifest_args!())
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+}
+
+/// Returns the ID of the NFT that represents the given `name`, computed like the blueprint does.
+fn name_id(name: &str) -> NonFungibleLocalId {
+    let hash = Sha256::digest(name);
+    let mut truncated_hash: [u8; 16] = Default::default();
+    truncated_hash.copy_from_slice(&hash[..16]);
+    let hash = u128::from_le_bytes(truncated_hash);
+    NonFungibleLocalId::bytes(hash.to_be_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn check_availability_reports_registered_unregistered_and_malformed_names() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("taken.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            test_environment.component_address,
+            "check_availability",
+            manifest_args!(vec![
+                "taken.xrd".to_string(),
+                "free.xrd".to_string(),
+                "malformed".to_string(),
+                ".xrd".to_string(),
+            ]),
+        )
+        .build();
+    let receipt = test_environment.execute(manifest);
+
+    let availability: Vec<(String, bool)> = receipt.expect_commit_success().output(1);
+    assert_eq!(
+        availability,
+        vec![
+            ("taken.xrd".to_string(), false),
+            ("free.xrd".to_string(), true),
+            ("malformed".to_string(), false),
+            (".xrd".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+fn unregistered_name_is_available_and_can_be_registered_again() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("again.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment.unregister_name().expect_commit_success();
+
+    assert_eq!(
+        test_environment.check_availability("again.xrd"),
+        ("again.xrd".to_string(), true)
+    );
+    test_environment
+        .lookup_address("again.xrd")
+        .expect_specific_failure(|error| format!("{:?}", error).contains("name not registered"));
+
+    test_environment
+        .register_name("again.xrd", 1, dec!(10))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.check_availability("again.xrd"),
+        ("again.xrd".to_string(), false)
+    );
+    test_environment
+        .lookup_address("again.xrd")
+        .expect_commit_success();
+}
+
+#[test]
+fn expired_name_no_longer_resolves() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("stale.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .lookup_address("stale.xrd")
+        .expect_commit_success();
+
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(15_001).unwrap());
+
+    test_environment
+        .lookup_address("stale.xrd")
+        .expect_specific_failure(|error| format!("{:?}", error).contains("name expired"));
+}
+
+#[test]
+fn lookup_with_ttl_caches_until_the_ttl_but_never_past_expiry() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("cached.xrd", 1, dec!(10))
+        .expect_commit_success();
+    let account_address = test_environment.account_address;
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+
+    // Without a TTL of its own, the name is cached for the default TTL
+    assert_eq!(
+        test_environment.lookup_with_ttl("cached.xrd"),
+        (account_address, current_epoch.number() + 100)
+    );
+
+    test_environment.set_ttl(500).expect_commit_success();
+    assert_eq!(
+        test_environment.lookup_with_ttl("cached.xrd"),
+        (account_address, current_epoch.number() + 500)
+    );
+
+    // Close to the expiry, the name may only be cached until it expires
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(14_800).unwrap());
+    assert_eq!(
+        test_environment.lookup_with_ttl("cached.xrd"),
+        (account_address, current_epoch.number() + 15_000)
+    );
+}
+
+#[test]
+fn lookup_addresses_resolves_only_registered_unexpired_names() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("old.xrd", 1, dec!(10))
+        .expect_commit_success();
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(10_000).unwrap());
+    test_environment
+        .register_name("new.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(15_001).unwrap());
+
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            test_environment.component_address,
+            "lookup_addresses",
+            manifest_args!(vec![
+                "new.xrd".to_string(),
+                "unknown.xrd".to_string(),
+                "old.xrd".to_string(),
+                "malformed".to_string(),
+            ]),
+        )
+        .build();
+    let receipt = test_environment.execute(manifest);
+
+    let addresses: Vec<(String, Option<ComponentAddress>)> =
+        receipt.expect_commit_success().output(1);
+    assert_eq!(
+        addresses,
+        vec![
+            (
+                "new.xrd".to_string(),
+                Some(test_environment.account_address)
+            ),
+            ("unknown.xrd".to_string(), None),
+            ("old.xrd".to_string(), None),
+            ("malformed".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn batch_methods_accept_at_most_the_maximum_batch_size() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    for (method_name, names_count, succeeds) in [
+        ("check_availability", 50, true),
+        ("lookup_addresses", 50, true),
+        ("check_availability", 51, false),
+        ("lookup_addresses", 51, false),
+    ] {
+        let names: Vec<String> = (0..names_count)
+            .map(|index| format!("name{}.xrd", index))
+            .collect();
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                test_environment.component_address,
+                method_name,
+                manifest_args!(names),
+            )
+            .build();
+        let receipt = test_environment.execute(manifest);
+
+        if succeeds {
+            let results: Vec<(String, ScryptoValue)> = receipt.expect_commit_success().output(1);
+            assert_eq!(results.len(), names_count);
+        } else {
+            receipt.expect_specific_failure(|error| {
+                format!("{:?}", error).contains("batch too large")
+            });
+        }
+    }
+}
+
+#[test]
+fn fees_can_be_inspected_and_withdrawn_to_an_account() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("fees.xrd", 1, dec!(10))
+        .expect_commit_success();
+    let new_address = test_environment.account_address;
+    test_environment
+        .update_address(new_address, dec!(5))
+        .expect_commit_success();
+
+    assert_eq!(test_environment.available_fees(), dec!(1));
+
+    let (_, _, treasury) = test_environment.test_runner.new_allocated_account();
+    let treasury_balance = test_environment
+        .test_runner
+        .get_component_balance(treasury, XRD);
+    test_environment
+        .call_as_admin("withdraw_fees_to", manifest_args!(treasury))
+        .expect_commit_success();
+
+    assert_eq!(test_environment.available_fees(), dec!(0));
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(treasury, XRD)
+            .checked_sub(treasury_balance)
+            .unwrap(),
+        dec!(1)
+    );
+}
+
+#[test]
+fn names_can_be_registered_with_a_custom_payment_token() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        custom_payment_token: true,
+        ..Default::default()
+    });
+    let payment_token = test_environment.payment_token;
+    let balance_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, payment_token);
+
+    test_environment
+        .register_name("stable.xrd", 2, dec!(25))
+        .expect_commit_success();
+
+    let balance_after = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, payment_token);
+    assert_eq!(balance_before.checked_sub(balance_after).unwrap(), dec!(20));
+    assert_eq!(
+        test_environment.test_runner.get_component_balance(
+            test_environment.account_address,
+            test_environment.name_resource
+        ),
+        dec!(1)
+    );
+}
+
+#[test]
+fn xrd_deposit_is_rejected_for_custom_payment_token() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        custom_payment_token: true,
+        ..Default::default()
+    });
+    test_environment.payment_token = XRD;
+
+    let receipt = test_environment.register_name("stable.xrd", 1, dec!(10));
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("The deposit must be made in")
+    });
+}
+
+#[test]
+fn frozen_name_resolves_but_rejects_updates_and_renewals() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("disputed.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .call_as_admin("freeze_name", manifest_args!("disputed.xrd".to_string()))
+        .expect_commit_success();
+
+    let new_address = test_environment.account_address;
+    test_environment
+        .update_address(new_address, dec!(1))
+        .expect_specific_failure(|error| format!("{:?}", error).contains("frozen"));
+    test_environment
+        .renew_name(1, dec!(2))
+        .expect_specific_failure(|error| format!("{:?}", error).contains("frozen"));
+    test_environment
+        .lookup_address("disputed.xrd")
+        .expect_commit_success();
+}
+
+#[test]
+fn frozen_name_can_not_be_unregistered() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("disputed.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .call_as_admin("freeze_name", manifest_args!("disputed.xrd".to_string()))
+        .expect_commit_success();
+
+    test_environment
+        .unregister_name()
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("This name is frozen pending a dispute")
+        });
+    assert_eq!(
+        test_environment.check_availability("disputed.xrd"),
+        ("disputed.xrd".to_string(), false)
+    );
+}
+
+#[test]
+fn unregistered_name_can_not_be_frozen() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .call_as_admin("freeze_name", manifest_args!("disputed.xrd".to_string()))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("Only registered names can be frozen")
+        });
+}
+
+#[test]
+fn frozen_expired_name_is_not_burned() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let account_address = test_environment.account_address;
+    test_environment
+        .register_name("disputed.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .call_as_admin("freeze_name", manifest_args!("disputed.xrd".to_string()))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(16_251);
+    let name_resource = test_environment.name_resource;
+    test_environment
+        .burn_expired_name("disputed.xrd", account_address)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(account_address, name_resource),
+        dec!(1)
+    );
+
+    // Once the freeze is lifted, the name is burned as usual
+    test_environment
+        .call_as_admin("unfreeze_name", manifest_args!("disputed.xrd".to_string()))
+        .expect_commit_success();
+    test_environment
+        .burn_expired_name("disputed.xrd", account_address)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(account_address, name_resource),
+        dec!(0)
+    );
+}
+
+#[test]
+fn unfreezing_a_name_restores_updates() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("disputed.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .call_as_admin("freeze_name", manifest_args!("disputed.xrd".to_string()))
+        .expect_commit_success();
+    test_environment
+        .call_as_admin("unfreeze_name", manifest_args!("disputed.xrd".to_string()))
+        .expect_commit_success();
+
+    let new_address = test_environment.account_address;
+    test_environment
+        .update_address(new_address, dec!(1))
+        .expect_commit_success();
+}
+
+#[test]
+fn registration_fails_while_paused() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .call_as_admin("set_paused", manifest_args!(true))
+        .expect_commit_success();
+
+    test_environment
+        .register_name("paused.xrd", 1, dec!(10))
+        .expect_specific_failure(|error| format!("{:?}", error).contains("service paused"));
+
+    test_environment
+        .call_as_admin("set_paused", manifest_args!(false))
+        .expect_commit_success();
+    test_environment
+        .register_name("paused.xrd", 1, dec!(10))
+        .expect_commit_success();
+}
+
+#[test]
+fn unregistering_refunds_the_deposit_while_paused() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("exit.xrd", 2, dec!(20))
+        .expect_commit_success();
+    test_environment
+        .call_as_admin("set_paused", manifest_args!(true))
+        .expect_commit_success();
+
+    test_environment
+        .lookup_address("exit.xrd")
+        .expect_commit_success();
+
+    let balance_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+    test_environment.unregister_name().expect_commit_success();
+    let balance_after = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+
+    assert_eq!(balance_after.checked_sub(balance_before).unwrap(), dec!(20));
+    assert_eq!(
+        test_environment.test_runner.get_component_balance(
+            test_environment.account_address,
+            test_environment.name_resource
+        ),
+        dec!(0)
+    );
+}
+
+#[test]
+fn early_unregister_retains_the_penalty_as_a_fee() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        early_unregister_penalty_percent: dec!(25),
+        min_holding_epochs: 100,
+        ..Default::default()
+    });
+    test_environment
+        .register_name("early.xrd", 2, dec!(20))
+        .expect_commit_success();
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(99).unwrap());
+
+    let balance_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+    test_environment.unregister_name().expect_commit_success();
+    let balance_after = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+
+    assert_eq!(balance_after.checked_sub(balance_before).unwrap(), dec!(15));
+    assert_eq!(test_environment.available_fees(), dec!(5));
+}
+
+#[test]
+fn late_unregister_refunds_the_full_deposit() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        early_unregister_penalty_percent: dec!(25),
+        min_holding_epochs: 100,
+        ..Default::default()
+    });
+    test_environment
+        .register_name("late.xrd", 2, dec!(20))
+        .expect_commit_success();
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(100).unwrap());
+
+    let balance_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+    test_environment.unregister_name().expect_commit_success();
+    let balance_after = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+
+    assert_eq!(balance_after.checked_sub(balance_before).unwrap(), dec!(20));
+    assert_eq!(test_environment.available_fees(), dec!(0));
+}
+
+#[test]
+fn burned_expired_name_can_be_registered_again() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let account_address = test_environment.account_address;
+    test_environment
+        .register_name("expired.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .set_text_record("url", "https://a.example")
+        .expect_commit_success();
+
+    // A name that has not expired yet is not burned
+    let report: CleanupReport = test_environment
+        .burn_expired_name("expired.xrd", account_address)
+        .expect_commit_success()
+        .output(2);
+    assert_eq!(report.burned_count, 0);
+
+    // Neither is a name in its grace period
+    test_environment.advance_epochs(15_001);
+    let report: CleanupReport = test_environment
+        .burn_expired_name("expired.xrd", account_address)
+        .expect_commit_success()
+        .output(2);
+    assert_eq!(report.burned_count, 0);
+
+    test_environment.advance_epochs(1_250);
+    let report: CleanupReport = test_environment
+        .burn_expired_name("expired.xrd", account_address)
+        .expect_commit_success()
+        .output(2);
+    assert_eq!(report.names, vec![name_id("expired.xrd")]);
+    assert_eq!(
+        test_environment.check_availability("expired.xrd"),
+        ("expired.xrd".to_string(), true)
+    );
+
+    // The name is re-issued when it is registered again, without any of its previous records
+    test_environment
+        .register_name("expired.xrd", 1, dec!(10))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(account_address, test_environment.name_resource),
+        dec!(1)
+    );
+    assert!(test_environment.list_records("expired.xrd").is_empty());
+    assert_eq!(
+        test_environment.check_availability("expired.xrd"),
+        ("expired.xrd".to_string(), false)
+    );
+    test_environment
+        .lookup_address("expired.xrd")
+        .expect_commit_success();
+}
+
+#[test]
+fn expired_names_are_burned_in_limited_batches() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let account_address = test_environment.account_address;
+    let names = ["first.xrd", "second.xrd", "third.xrd"];
+    for name in names {
+        test_environment
+            .register_name(name, 1, dec!(10))
+            .expect_commit_success();
+    }
+    test_environment.advance_epochs(16_251);
+
+    let report: CleanupReport = test_environment
+        .burn_expired_names(&names, account_address, Some(2))
+        .expect_commit_success()
+        .output(2);
+    assert_eq!(report.burned_count, 2);
+    assert_eq!(report.names.len(), 2);
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(account_address, test_environment.name_resource),
+        dec!(1)
+    );
+
+    // Names that were already burned are skipped
+    let report: CleanupReport = test_environment
+        .burn_expired_names(&names, account_address, Some(2))
+        .expect_commit_success()
+        .output(2);
+    assert_eq!(report.burned_count, 1);
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(account_address, test_environment.name_resource),
+        dec!(0)
+    );
+}
+
+#[test]
+fn unregistering_a_watched_name_notifies_its_watchers() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (watcher_key, _, watcher) = test_environment.test_runner.new_virtual_account();
+    test_environment
+        .register_name("wanted.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .watch_name("wanted.xrd", watcher_key, watcher)
+        .expect_commit_success();
+
+    let receipt = test_environment.unregister_name();
+    let notifications = test_environment.watched_name_notifications(&receipt);
+
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].1, vec![watcher]);
+}
+
+#[test]
+fn burning_an_expired_watched_name_notifies_its_watchers() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (watcher_key, _, watcher) = test_environment.test_runner.new_virtual_account();
+    let account_address = test_environment.account_address;
+    test_environment
+        .register_name("wanted.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .watch_name("wanted.xrd", watcher_key, watcher)
+        .expect_commit_success();
+
+    test_environment.advance_epochs(16_251);
+    let receipt = test_environment.burn_expired_name("wanted.xrd", account_address);
+    let notifications = test_environment.watched_name_notifications(&receipt);
+
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].1, vec![watcher]);
+}
+
+#[test]
+fn watchers_of_a_name_are_limited_but_can_watch_it_again() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("wanted.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    let mut watchers = Vec::new();
+    for _ in 0..20 {
+        let (watcher_key, _, watcher) = test_environment.test_runner.new_virtual_account();
+        test_environment
+            .watch_name("wanted.xrd", watcher_key, watcher)
+            .expect_commit_success();
+        watchers.push((watcher_key, watcher));
+    }
+
+    let (watcher_key, _, watcher) = test_environment.test_runner.new_virtual_account();
+    test_environment
+        .watch_name("wanted.xrd", watcher_key, watcher)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("This name already has the maximum of 20 watchers")
+        });
+    let (watcher_key, watcher) = watchers[0];
+    test_environment
+        .watch_name("wanted.xrd", watcher_key, watcher)
+        .expect_commit_success();
+}
+
+#[test]
+fn names_can_not_be_watched_for_another_account() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (_, _, watcher) = test_environment.test_runner.new_virtual_account();
+    let public_key = test_environment.public_key;
+    test_environment
+        .register_name("wanted.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .watch_name("wanted.xrd", public_key, watcher)
+        .expect_commit_failure();
+}
+
+#[test]
+fn unregistered_names_can_not_be_watched() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (watcher_key, _, watcher) = test_environment.test_runner.new_virtual_account();
+
+    test_environment
+        .watch_name("free.xrd", watcher_key, watcher)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("Only registered names can be watched")
+        });
+}
+
+#[test]
+fn huge_reservation_period_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    test_environment
+        .register_name("forever.xrd", 200, dec!(2000))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("reservation period too long")
+        });
+}
+
+#[test]
+fn huge_renewal_period_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("forever.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .renew_name(200, dec!(400))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("reservation period too long")
+        });
+    test_environment
+        .renew_name(1, dec!(2))
+        .expect_commit_success();
+}
+
+#[test]
+fn ownership_is_verified_for_an_owned_name() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("mine.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    assert!(test_environment.verify_ownership("mine.xrd"));
+}
+
+#[test]
+fn ownership_is_not_verified_for_a_different_name() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("mine.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    assert!(!test_environment.verify_ownership("theirs.xrd"));
+}
+
+#[test]
+fn ownership_is_not_verified_for_an_expired_name() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("mine.xrd", 1, dec!(10))
+        .expect_commit_success();
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(15_001).unwrap());
+
+    assert!(!test_environment.verify_ownership("mine.xrd"));
+}
+
+#[test]
+fn name_in_grace_period_is_auto_renewed_from_its_credit() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("kept.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .deposit_renewal_credit(dec!(5))
+        .expect_commit_success();
+
+    // The name can not be renewed automatically before it expires
+    test_environment
+        .auto_renew("kept.xrd")
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("can only be renewed automatically")
+        });
+
+    test_environment.advance_epochs(15_001);
+    assert!(!test_environment.verify_ownership("kept.xrd"));
+
+    let receipt = test_environment.auto_renew("kept.xrd");
+    let commit = receipt.expect_commit_success();
+    let renewals: Vec<(String, Epoch)> = commit
+        .application_events
+        .iter()
+        .filter(|(event_type_identifier, _)| {
+            test_environment
+                .test_runner
+                .event_name(event_type_identifier)
+                == "AutoRenewedEvent"
+        })
+        .map(|(_, event_data)| scrypto_decode(event_data).unwrap())
+        .collect();
+
+    assert_eq!(renewals.len(), 1);
+    assert_eq!(renewals[0].0, "kept.xrd");
+    assert!(test_environment.verify_ownership("kept.xrd"));
+    assert_eq!(test_environment.available_fees(), dec!(2));
+}
+
+#[test]
+fn auto_renewal_fails_without_sufficient_credit() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("kept.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .deposit_renewal_credit(dec!(1))
+        .expect_commit_success();
+    test_environment.advance_epochs(15_001);
+
+    test_environment
+        .auto_renew("kept.xrd")
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("Insufficient renewal credit")
+        });
+}
+
+#[test]
+fn rapid_second_registration_is_blocked_by_the_cooldown() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        registration_cooldown_epochs: 100,
+        ..Default::default()
+    });
+    test_environment
+        .register_name("first.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(40);
+    test_environment
+        .register_name("second.xrd", 1, dec!(10))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("registration cooldown: wait 60 more epochs")
+        });
+
+    test_environment.advance_epochs(60);
+    test_environment
+        .register_name("second.xrd", 1, dec!(10))
+        .expect_commit_success();
+}
+
+#[test]
+fn registration_cooldown_does_not_affect_other_accounts() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        registration_cooldown_epochs: 100,
+        ..Default::default()
+    });
+    let (other_public_key, _, other_account) = test_environment.test_runner.new_virtual_account();
+
+    test_environment
+        .register_name("first.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .register_name_as(
+            other_public_key,
+            other_account,
+            other_account,
+            "second.xrd",
+            1,
+            dec!(10),
+        )
+        .expect_commit_success();
+}
+
+#[test]
+fn registration_cooldown_can_not_be_bypassed_with_another_registrant() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        registration_cooldown_epochs: 100,
+        ..Default::default()
+    });
+    let (_, _, other_account) = test_environment.test_runner.new_virtual_account();
+    test_environment
+        .register_name("first.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    // The owner of the registrant account must be present
+    test_environment
+        .register_name_for(other_account, "second.xrd", 1, dec!(10))
+        .expect_commit_failure();
+}
+
+#[test]
+fn holder_proof_discounts_the_renewal_fee() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        loyalty_discount_percent: dec!(25),
+        ..Default::default()
+    });
+    test_environment
+        .register_name("loyal.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .renew_name(2, dec!(4))
+        .expect_commit_success();
+    assert_eq!(test_environment.available_fees(), dec!(4));
+
+    test_environment
+        .register_name("other.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .renew_name_with_holder_proof("loyal.xrd", "other.xrd", 2, dec!(4))
+        .expect_commit_success();
+    assert_eq!(test_environment.available_fees(), dec!(7));
+}
+
+#[test]
+fn holder_proof_of_the_renewed_name_gets_no_discount() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        loyalty_discount_percent: dec!(25),
+        ..Default::default()
+    });
+    test_environment
+        .register_name("loyal.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .renew_name_with_holder_proof("loyal.xrd", "loyal.xrd", 2, dec!(4))
+        .expect_commit_success();
+    assert_eq!(test_environment.available_fees(), dec!(4));
+}
+
+#[test]
+fn expired_holder_proof_gets_no_discount() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        loyalty_discount_percent: dec!(25),
+        ..Default::default()
+    });
+    test_environment
+        .register_name("loyal.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .register_name("other.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment.advance_epochs(15_001);
+
+    test_environment
+        .renew_name_with_holder_proof("loyal.xrd", "other.xrd", 2, dec!(4))
+        .expect_commit_success();
+    assert_eq!(test_environment.available_fees(), dec!(4));
+}
+
+#[test]
+fn text_records_are_overwritten_and_deleted_by_an_empty_value() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("records.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .set_text_record("url", "https://a.example")
+        .expect_commit_success();
+    test_environment
+        .set_text_record("email", "me@example.com")
+        .expect_commit_success();
+    test_environment
+        .set_text_record("url", "https://b.example")
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.list_records("records.xrd"),
+        vec![
+            ("email".to_string(), "me@example.com".to_string()),
+            ("url".to_string(), "https://b.example".to_string()),
+        ]
+    );
+
+    test_environment
+        .set_text_record("email", "")
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.list_records("records.xrd"),
+        vec![("url".to_string(), "https://b.example".to_string())]
+    );
+}
+
+#[test]
+fn text_records_are_limited_in_number_and_length() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        max_records_per_name: 2,
+        max_value_length: 10,
+        ..Default::default()
+    });
+    test_environment
+        .register_name("records.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .set_text_record("a", "1")
+        .expect_commit_success();
+    test_environment
+        .set_text_record("b", "2")
+        .expect_commit_success();
+    test_environment
+        .set_text_record("c", "3")
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("A name can hold at most 2 text records")
+        });
+
+    // Overwriting an existing record does not count against the limit
+    test_environment
+        .set_text_record("b", "0123456789")
+        .expect_commit_success();
+    test_environment
+        .set_text_record("b", "0123456789a")
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The record value can be at most 10 bytes long")
+        });
+}
+
+#[test]
+fn text_record_keys_are_limited_in_length() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .register_name("records.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    test_environment
+        .set_text_record(&"k".repeat(64), "1")
+        .expect_commit_success();
+    test_environment
+        .set_text_record(&"k".repeat(65), "1")
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The record key can be at most 64 bytes long")
+        });
+}
+
+#[test]
+fn whitelist_only_registration_admits_only_whitelisted_accounts() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        whitelist_only: true,
+        ..Default::default()
+    });
+    let whitelisted_account = test_environment.account_address;
+    let (other_public_key, _, other_account) = test_environment.test_runner.new_virtual_account();
+    test_environment
+        .call_as_admin("add_to_whitelist", manifest_args!(whitelisted_account))
+        .expect_commit_success();
+
+    test_environment
+        .register_name("early.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .register_name_as(
+            other_public_key,
+            other_account,
+            other_account,
+            "late.xrd",
+            1,
+            dec!(10),
+        )
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("registration restricted to whitelist")
+        });
+
+    // Once the whitelist mode is turned off, everybody can register again
+    test_environment
+        .call_as_admin("set_whitelist_only", manifest_args!(false))
+        .expect_commit_success();
+    test_environment
+        .register_name_as(
+            other_public_key,
+            other_account,
+            other_account,
+            "late.xrd",
+            1,
+            dec!(10),
+        )
+        .expect_commit_success();
+}
+
+#[test]
+fn deposit_of_burned_expired_name_is_refunded_to_its_registrant() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let account_address = test_environment.account_address;
+    test_environment
+        .register_name("expired.xrd", 1, dec!(10))
+        .expect_commit_success();
+
+    // A name within its grace period keeps its deposit
+    test_environment.advance_epochs(15_001);
+    test_environment
+        .burn_expired_name("expired.xrd", account_address)
+        .expect_commit_success();
+    test_environment
+        .claim_expired_refund(account_address)
+        .expect_commit_failure();
+
+    test_environment.advance_epochs(1_250);
+    test_environment
+        .burn_expired_name("expired.xrd", account_address)
+        .expect_commit_success();
+
+    let balance_before = test_environment
+        .test_runner
+        .get_component_balance(account_address, test_environment.payment_token);
+    test_environment
+        .claim_expired_refund(account_address)
+        .expect_commit_success();
+    let balance_after = test_environment
+        .test_runner
+        .get_component_balance(account_address, test_environment.payment_token);
+    assert_eq!(balance_after.checked_sub(balance_before).unwrap(), dec!(10));
+    assert_eq!(test_environment.available_fees(), dec!(0));
+
+    // The name was recalled and burned
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(account_address, test_environment.name_resource),
+        dec!(0)
+    );
+}
+
+#[test]
+fn expired_refund_can_only_be_claimed_by_the_registrant() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (registrant_public_key, _, registrant_account) =
+        test_environment.test_runner.new_virtual_account();
+    test_environment
+        .register_name_as(
+            registrant_public_key,
+            registrant_account,
+            registrant_account,
+            "expired.xrd",
+            1,
+            dec!(10),
+        )
+        .expect_commit_success();
+    test_environment.advance_epochs(16_251);
+    test_environment
+        .burn_expired_name("expired.xrd", registrant_account)
+        .expect_commit_success();
+
+    test_environment
+        .claim_expired_refund(registrant_account)
+        .expect_commit_failure();
+}
+
+#[test]
+fn unused_renewal_credit_is_refunded_to_its_depositor() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (public_key, account_address) = (
+        test_environment.public_key,
+        test_environment.account_address,
+    );
+    let (other_public_key, _, other_account) = test_environment.test_runner.new_virtual_account();
+    test_environment
+        .register_name("kept.xrd", 1, dec!(10))
+        .expect_commit_success();
+    test_environment
+        .deposit_renewal_credit(dec!(3))
+        .expect_commit_success();
+
+    // The name is transferred to another account, which tops up its renewal credit
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(account_address, test_environment.name_resource, dec!(1))
+        .deposit_batch(other_account)
+        .build();
+    test_environment
+        .test_runner
+        .execute_manifest_ignoring_fee(
+            manifest,
+            vec![
+                NonFungibleGlobalId::from_public_key(&public_key),
+                NonFungibleGlobalId::from_public_key(&other_public_key),
+            ],
+        )
+        .expect_commit_success();
+    test_environment
+        .deposit_renewal_credit_as(other_public_key, other_account, dec!(5))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(16_251);
+    test_environment
+        .burn_expired_name("kept.xrd", other_account)
+        .expect_commit_success();
+
+    // The registrant gets back the deposit and its own credit, the new holder only its credit
+    for (public_key, account, refund) in [
+        (public_key, account_address, dec!(13)),
+        (other_public_key, other_account, dec!(5)),
+    ] {
+        let balance_before = test_environment
+            .test_runner
+            .get_component_balance(account, test_environment.payment_token);
+        test_environment
+            .claim_expired_refund_as(public_key, account)
+            .expect_commit_success();
+        let balance_after = test_environment
+            .test_runner
+            .get_component_balance(account, test_environment.payment_token);
+        assert_eq!(balance_after.checked_sub(balance_before).unwrap(), refund);
+    }
+}
+
+#[test]
+fn names_per_account_are_limited_until_a_name_is_unregistered() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(RnsArguments {
+        max_names_per_account: 2,
+        ..Default::default()
+    });
+    let registrant_account = test_environment.account_address;
+
+    for name in ["first.xrd", "second.xrd"] {
+        test_environment
+            .register_name(name, 1, dec!(10))
+            .expect_commit_success();
+    }
+    assert_eq!(test_environment.names_held_count(registrant_account), 2);
+    test_environment
+        .register_name("third.xrd", 1, dec!(10))
+        .expect_specific_failure(|error| format!("{:?}", error).contains("name limit reached"));
+
+    // Unregistering a name frees up a slot
+    test_environment.unregister_name().expect_commit_success();
+    assert_eq!(test_environment.names_held_count(registrant_account), 1);
+    test_environment
+        .register_name("third.xrd", 1, dec!(10))
+        .expect_commit_success();
+    assert_eq!(test_environment.names_held_count(registrant_account), 2);
+}
+
+#[test]
+fn presale_reservation_is_credited_towards_the_deposit() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .reserve_presale("early.xrd", dec!(4))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The presale is not open")
+        });
+    test_environment
+        .call_as_admin("open_presale", manifest_args!(dec!(4), 100u64))
+        .expect_commit_success();
+
+    let balance_before = test_environment.test_runner.get_component_balance(
+        test_environment.account_address,
+        test_environment.payment_token,
+    );
+    test_environment
+        .reserve_presale("early.xrd", dec!(4))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.test_runner.get_component_balance(
+            test_environment.account_address,
+            test_environment.reservation_resource
+        ),
+        dec!(1)
+    );
+
+    // Nobody can register the reserved name without the reservation
+    test_environment
+        .register_name("early.xrd", 1, dec!(10))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("This name is reserved in the presale")
+        });
+
+    // The deposit for one year is 10, of which 4 were already paid in the presale
+    test_environment
+        .register_reserved_name("early.xrd", 1, dec!(6))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.test_runner.get_component_balance(
+            test_environment.account_address,
+            test_environment.payment_token
+        ),
+        balance_before.checked_sub(dec!(10)).unwrap()
+    );
+    assert_eq!(
+        test_environment.test_runner.get_component_balance(
+            test_environment.account_address,
+            test_environment.reservation_resource
+        ),
+        dec!(0)
+    );
+    assert!(test_environment.verify_ownership("early.xrd"));
+}
+
+#[test]
+fn expired_presale_reservation_frees_the_name_and_is_refunded() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .call_as_admin("open_presale", manifest_args!(dec!(4), 10u64))
+        .expect_commit_success();
+    let balance_before = test_environment.test_runner.get_component_balance(
+        test_environment.account_address,
+        test_environment.payment_token,
+    );
+    test_environment
+        .reserve_presale("early.xrd", dec!(4))
+        .expect_commit_success();
+
+    test_environment
+        .refund_reservation()
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The reservation has not expired yet")
+        });
+
+    test_environment.advance_epochs(11);
+    test_environment
+        .register_reserved_name("early.xrd", 1, dec!(6))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The reservation has expired")
+        });
+
+    // After the reservation expired, the name can be registered without it
+    test_environment
+        .register_name("early.xrd", 1, dec!(10))
+        .expect_commit_success();
+    assert!(test_environment.verify_ownership("early.xrd"));
+
+    // The amount paid in the presale is refunded in full
+    test_environment
+        .refund_reservation()
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.test_runner.get_component_balance(
+            test_environment.account_address,
+            test_environment.payment_token
+        ),
+        balance_before.checked_sub(dec!(10)).unwrap()
+    );
+    assert_eq!(
+        test_environment.test_runner.get_component_balance(
+            test_environment.account_address,
+            test_environment.reservation_resource
+        ),
+        dec!(0)
+    );
+}
+
+#[test]
+fn name_of_an_expired_reservation_can_be_reserved_again() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .call_as_admin("open_presale", manifest_args!(dec!(4), 10u64))
+        .expect_commit_success();
+    test_environment
+        .reserve_presale("early.xrd", dec!(4))
+        .expect_commit_success();
+    test_environment.advance_epochs(11);
+
+    // The expired reservation was not refunded, but no longer blocks the name
+    let (other_public_key, _, other_account) = test_environment.test_runner.new_virtual_account();
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(other_account, test_environment.payment_token, dec!(4))
+        .take_all_from_worktop(test_environment.payment_token, "payment")
+        .call_method_with_name_lookup(
+            test_environment.component_address,
+            "reserve_presale",
+            |lookup| ("early.xrd".to_string(), lookup.bucket("payment")),
+        )
+        .deposit_batch(other_account)
+        .build();
+    test_environment
+        .test_runner
+        .execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&other_public_key)],
+        )
+        .expect_commit_success();
+
+    // Refunding the expired reservation leaves the new one in place
+    test_environment
+        .refund_reservation()
+        .expect_commit_success();
+    test_environment
+        .register_name("early.xrd", 1, dec!(10))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("This name is reserved in the presale")
+        });
+}
+
+#[test]
+fn reserved_name_can_not_be_reserved_twice() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .call_as_admin("open_presale", manifest_args!(dec!(4), 100u64))
+        .expect_commit_success();
+
+    test_environment
+        .reserve_presale("early.xrd", dec!(4))
+        .expect_commit_success();
+    test_environment
+        .reserve_presale("early.xrd", dec!(8))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("This name is already taken")
+        });
+}
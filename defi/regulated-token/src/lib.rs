@@ -1,6 +1,76 @@
+use account_auth::assert_account_owner;
 use scrypto::prelude::*;
 
+/// A summary of the current sale conditions, intended for UIs.
+#[derive(ScryptoSbor, Debug, PartialEq, Eq)]
+pub struct SaleInfo {
+    pub current_stage: u8,
+    pub current_price: Decimal,
+    /// The epoch at which the stage 1 discount ends, if the discount is time limited
+    pub discount_until: Option<u64>,
+    /// The scheduled freeze change, if any: whether transfers will be frozen, and the epoch from
+    /// which the change can be applied
+    pub pending_freeze_change: Option<(bool, u64)>,
+}
+
+/// The optional features of a regulated token sale.
+#[derive(ScryptoSbor, Debug, Clone)]
+pub struct SaleConfig {
+    /// If set, the stage 1 discount only applies until this epoch is reached, otherwise it lasts for all of stage 1.
+    pub discount_until: Option<u64>,
+    /// If set, no more than this many tokens may be minted during stage 2.
+    pub stage2_mint_cap: Option<Decimal>,
+    /// Whether every purchase also returns a soulbound purchase receipt NFT.
+    pub issue_receipts: bool,
+    /// If set, every payment is deposited straight into this account instead of being collected by the component.
+    pub instant_payout: Option<ComponentAddress>,
+    /// Token supply and collected XRD below this threshold may be swept by the admin.
+    pub dust_threshold: Decimal,
+    /// If set, every purchase is reported to the `report_sale` method of this component, which may veto the purchase
+    /// by panicking.
+    pub compliance_hook: Option<ComponentAddress>,
+    /// The token's "stage" metadata shows the entry of these descriptions for the current stage, or the default stage
+    /// name if there is no entry for it.
+    pub stage_descriptions: Vec<String>,
+    /// If set, no more than this many tokens may be bought in a single epoch.
+    pub max_sold_per_epoch: Option<Decimal>,
+    /// If not zero, freezing or unfreezing transfers only takes effect this many epochs after it was requested, giving
+    /// holders notice of the change.
+    pub freeze_change_notice_epochs: u64,
+}
+
+/// A snapshot of the whole component state, intended for debugging and explorers.
+#[derive(ScryptoSbor, Debug, PartialEq, Eq)]
+pub struct ComponentInfo {
+    pub current_stage: u8,
+    pub token_supply_amount: Decimal,
+    /// Whether the component still has authority over the token. It gives it up in stage 3,
+    /// when the token behavior is locked.
+    pub internal_authority_present: bool,
+    pub collected_xrd: Decimal,
+    pub admin_badge_address: ResourceAddress,
+    pub freeze_admin_badge_address: ResourceAddress,
+    pub token_address: ResourceAddress,
+}
+
+/// The data of a purchase receipt, an audit record of a single `buy_token` call. The buyer is the
+/// account whose owner was present when the purchase was made.
+#[derive(NonFungibleData, ScryptoSbor)]
+pub struct PurchaseReceipt {
+    pub buyer: ComponentAddress,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub epoch: Epoch,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct SupplyMintedEvent {
+    amount: Decimal,
+    total_minted: Decimal,
+}
+
 #[blueprint]
+#[events(SupplyMintedEvent)]
 mod regulated_token {
     enable_method_auth! {
         roles {
@@ -9,9 +79,19 @@ mod regulated_token {
         },
         methods {
             toggle_transfer_freeze => restrict_to: [freeze_admin];
+            cancel_pending_freeze_change => restrict_to: [freeze_admin];
+            apply_pending_freeze_change => PUBLIC;
+            add_exempt => restrict_to: [general_admin];
+            remove_exempt => restrict_to: [general_admin];
             collect_payments => restrict_to: [general_admin];
+            swap_collected => restrict_to: [general_admin];
+            sweep_residuals => restrict_to: [general_admin];
             advance_stage => restrict_to: [general_admin];
             get_current_stage => PUBLIC;
+            get_sale_info => PUBLIC;
+            get_component_info => PUBLIC;
+            remaining_stage2_mint => PUBLIC;
+            remaining_this_epoch => PUBLIC;
             buy_token => PUBLIC;
         }
     }
@@ -21,11 +101,58 @@ mod regulated_token {
         current_stage: u8,
         admin_badge_address: ResourceAddress,
         freeze_admin_badge_address: ResourceAddress,
+        discount_until: Option<u64>,
+        stage2_mint_cap: Option<Decimal>,
+        stage2_minted: Decimal,
+        receipt_resource_address: Option<ResourceAddress>,
+        instant_payout: Option<ComponentAddress>,
+        dust_threshold: Decimal,
+        compliance_hook: Option<ComponentAddress>,
+        stage_descriptions: Vec<String>,
+        max_sold_per_epoch: Option<Decimal>,
+        sold_this_epoch: Decimal,
+        sold_epoch: Epoch,
+        transfers_frozen: bool,
+        freeze_exempt: HashSet<ComponentAddress>,
+        freeze_change_notice_epochs: u64,
+        pending_freeze_change: Option<(bool, u64)>,
     }
 
     impl RegulatedToken {
+        /// Creates a new regulated token sale with the optional features given by `config`. See `SaleConfig` for a
+        /// description of each of them.
         pub fn instantiate_regulated_token(
+            config: SaleConfig,
         ) -> (Global<RegulatedToken>, FungibleBucket, FungibleBucket) {
+            let SaleConfig {
+                discount_until,
+                stage2_mint_cap,
+                issue_receipts,
+                instant_payout,
+                dust_threshold,
+                compliance_hook,
+                stage_descriptions,
+                max_sold_per_epoch,
+                freeze_change_notice_epochs,
+            } = config;
+
+            if let Some(stage2_mint_cap) = stage2_mint_cap {
+                assert!(
+                    stage2_mint_cap >= dec!("0"),
+                    "The stage 2 mint cap can't be negative"
+                );
+            }
+            assert!(
+                dust_threshold >= dec!("0"),
+                "The dust threshold can't be negative"
+            );
+            if let Some(max_sold_per_epoch) = max_sold_per_epoch {
+                assert!(
+                    max_sold_per_epoch >= dec!("0"),
+                    "The per-epoch sale limit can't be negative"
+                );
+            }
+
             // We are allocating a ComponentAddress used for our actor virtual badge and provide
             // minting & transfer authority to our component.
             let (address_reservation, component_address) =
@@ -75,7 +202,7 @@ mod regulated_token {
                     init {
                         "name" => "Regulo".to_string(), locked;
                         "symbol" => "REG".to_string(), locked;
-                        "stage" => "Stage 1 - Fixed supply, may be restricted transfer".to_string(), updatable;
+                        "stage" => Self::stage_description(&stage_descriptions, 1), updatable;
                     }
                 ))
                 .freeze_roles(freeze_roles!(
@@ -83,7 +210,11 @@ mod regulated_token {
                     freezer_updater => access_rule.clone();
                 ))
                 .withdraw_roles(withdraw_roles!(
-                    withdrawer => rule!(require(freeze_admin.resource_address()));
+                    withdrawer => Self::frozen_withdraw_rule(
+                        freeze_admin.resource_address(),
+                        component_address,
+                        &HashSet::new(),
+                    );
                     withdrawer_updater => access_rule.clone();
                 ))
                 .recall_roles(recall_roles!(
@@ -96,12 +227,51 @@ mod regulated_token {
                 ))
                 .mint_initial_supply(100);
 
+            // The purchase receipts are minted by our component only, and may never be withdrawn from
+            // the account they were first deposited to
+            let receipt_resource_address = if issue_receipts {
+                let receipt_resource_manager =
+                    ResourceBuilder::new_ruid_non_fungible::<PurchaseReceipt>(OwnerRole::None)
+                        .metadata(metadata! (
+                            init {
+                                "name" => "Regulo purchase receipt".to_string(), locked;
+                            }
+                        ))
+                        .mint_roles(mint_roles!(
+                            minter => rule!(require(global_caller(component_address)));
+                            minter_updater => rule!(deny_all);
+                        ))
+                        .withdraw_roles(withdraw_roles!(
+                            withdrawer => rule!(deny_all);
+                            withdrawer_updater => rule!(deny_all);
+                        ))
+                        .create_with_no_initial_supply();
+                Some(receipt_resource_manager.address())
+            } else {
+                None
+            };
+
             let component = Self {
                 token_supply: Vault::with_bucket(regulated_tokens.into()),
                 collected_xrd: Vault::new(XRD),
                 current_stage: 1,
                 admin_badge_address: general_admin.resource_address(),
                 freeze_admin_badge_address: freeze_admin.resource_address(),
+                discount_until,
+                stage2_mint_cap,
+                stage2_minted: dec!("0"),
+                receipt_resource_address,
+                instant_payout,
+                dust_threshold,
+                compliance_hook,
+                stage_descriptions,
+                max_sold_per_epoch,
+                sold_this_epoch: dec!("0"),
+                sold_epoch: Runtime::current_epoch(),
+                transfers_frozen: true,
+                freeze_exempt: HashSet::new(),
+                freeze_change_notice_epochs,
+                pending_freeze_change: None,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::None)
@@ -116,30 +286,172 @@ mod regulated_token {
         }
 
         /// The freeze admin badge may be used to freeze or unfreeze consumer transfers of the supply
-        pub fn toggle_transfer_freeze(&self, set_frozen: bool) {
-            // Note that this operation will fail if the token has reached stage 3 and the token behavior has been locked
-            let token_resource_manager = self.token_supply.resource_manager();
-
-            if set_frozen {
-                token_resource_manager
-                    .set_withdrawable(rule!(require(self.freeze_admin_badge_address)));
-                info!("Token transfer is now RESTRICTED");
+        /// While frozen, only the freeze admin, this component and the freeze-exempt addresses may move tokens
+        /// If a notice period is configured, the change is only scheduled, replacing any change scheduled
+        /// before, and can be applied through `apply_pending_freeze_change` once the notice period has passed
+        pub fn toggle_transfer_freeze(&mut self, set_frozen: bool) {
+            // The component gives up its authority over the token in stage 3, so a scheduled change could never be applied
+            assert!(
+                self.current_stage < 3,
+                "The transfer freeze can't be changed in the final stage"
+            );
+            if self.freeze_change_notice_epochs == 0 {
+                self.set_transfer_freeze(set_frozen);
             } else {
-                token_resource_manager.set_withdrawable(rule!(allow_all));
-                info!("Token is now freely transferrable");
+                let effective_epoch = Runtime::current_epoch()
+                    .number()
+                    .checked_add(self.freeze_change_notice_epochs)
+                    .unwrap();
+                self.pending_freeze_change = Some((set_frozen, effective_epoch));
+                info!(
+                    "Token transfer freeze change scheduled for epoch {}",
+                    effective_epoch
+                );
             }
         }
 
+        /// Applies the scheduled freeze change once its notice period has passed. Anyone may do so.
+        pub fn apply_pending_freeze_change(&mut self) {
+            let (set_frozen, effective_epoch) = self
+                .pending_freeze_change
+                .expect("No freeze change is scheduled");
+            assert!(
+                Runtime::current_epoch().number() >= effective_epoch,
+                "The scheduled freeze change only takes effect at epoch {}",
+                effective_epoch
+            );
+
+            self.pending_freeze_change = None;
+            self.set_transfer_freeze(set_frozen);
+        }
+
+        /// Reverts the scheduled freeze change, leaving the current freeze state as it is
+        pub fn cancel_pending_freeze_change(&mut self) {
+            assert!(
+                self.pending_freeze_change.take().is_some(),
+                "No freeze change is scheduled"
+            );
+            info!("Scheduled token transfer freeze change cancelled");
+        }
+
+        /// Allows the given address, such as a DEX or a custodian, to keep moving tokens while transfers are frozen
+        pub fn add_exempt(&mut self, address: ComponentAddress) {
+            assert!(
+                self.freeze_exempt.insert(address),
+                "{:?} is already exempt from transfer freezes",
+                address
+            );
+            self.update_frozen_withdraw_rule();
+        }
+
+        /// Subjects the given address to transfer freezes again
+        pub fn remove_exempt(&mut self, address: ComponentAddress) {
+            assert!(
+                self.freeze_exempt.remove(&address),
+                "{:?} is not exempt from transfer freezes",
+                address
+            );
+            self.update_frozen_withdraw_rule();
+        }
+
         pub fn get_current_stage(&self) -> u8 {
             info!("Current stage is {}", self.current_stage);
             self.current_stage
         }
 
+        pub fn get_sale_info(&self) -> SaleInfo {
+            SaleInfo {
+                current_stage: self.current_stage,
+                current_price: self.current_price(),
+                discount_until: self.discount_until,
+                pending_freeze_change: self.pending_freeze_change,
+            }
+        }
+
+        pub fn get_component_info(&self) -> ComponentInfo {
+            ComponentInfo {
+                current_stage: self.current_stage,
+                token_supply_amount: self.token_supply.amount(),
+                internal_authority_present: self.current_stage < 3,
+                collected_xrd: self.collected_xrd.amount(),
+                admin_badge_address: self.admin_badge_address,
+                freeze_admin_badge_address: self.freeze_admin_badge_address,
+                token_address: self.token_supply.resource_address(),
+            }
+        }
+
+        /// How many more tokens may be minted during stage 2, or `None` if stage 2 minting is uncapped
+        pub fn remaining_stage2_mint(&self) -> Option<Decimal> {
+            self.stage2_mint_cap
+                .map(|cap| cap.checked_sub(self.stage2_minted).unwrap())
+        }
+
+        /// How many more tokens may be bought in the current epoch, or `Decimal::MAX` if there is no
+        /// per-epoch sale limit
+        pub fn remaining_this_epoch(&self) -> Decimal {
+            match self.max_sold_per_epoch {
+                Some(max_sold_per_epoch) if Runtime::current_epoch() == self.sold_epoch => {
+                    max_sold_per_epoch
+                        .checked_sub(self.sold_this_epoch)
+                        .unwrap()
+                }
+                Some(max_sold_per_epoch) => max_sold_per_epoch,
+                None => Decimal::MAX,
+            }
+        }
+
         /// Permit the proper authority to withdraw our collected XRD
         pub fn collect_payments(&mut self) -> Bucket {
             self.collected_xrd.take_all()
         }
 
+        /// Swaps `amount_in` of the collected XRD for another token through the swap method of the
+        /// given `dex` component. Fails if the DEX pays out less than `min_out`.
+        pub fn swap_collected(
+            &mut self,
+            dex: ComponentAddress,
+            min_out: Decimal,
+            amount_in: Decimal,
+        ) -> Bucket {
+            assert!(
+                amount_in <= self.collected_xrd.amount(),
+                "Can't swap {} XRD, only {} XRD has been collected",
+                amount_in,
+                self.collected_xrd.amount()
+            );
+
+            let dex: Global<AnyComponent> = Global::from(dex);
+            let output: Bucket =
+                dex.call_raw("swap", scrypto_args!(self.collected_xrd.take(amount_in)));
+
+            assert!(
+                output.amount() >= min_out,
+                "Slippage too high: the swap returned {} but at least {} was required",
+                output.amount(),
+                min_out
+            );
+
+            output
+        }
+
+        /// Permit the proper authority to clean up the residual token supply and collected XRD left
+        /// behind by fractional purchases. Each vault is only emptied if it holds less than the dust
+        /// threshold, otherwise an empty bucket is returned for it.
+        pub fn sweep_residuals(&mut self) -> (Bucket, Bucket) {
+            let token_residual = if self.token_supply.amount() < self.dust_threshold {
+                self.token_supply.take_all()
+            } else {
+                Bucket::new(self.token_supply.resource_address())
+            };
+            let xrd_residual = if self.collected_xrd.amount() < self.dust_threshold {
+                self.collected_xrd.take_all()
+            } else {
+                Bucket::new(self.collected_xrd.resource_address())
+            };
+
+            (token_residual, xrd_residual)
+        }
+
         pub fn advance_stage(&mut self) {
             assert!(self.current_stage <= 2, "Already at final stage");
             let token_resource_manager = self.token_supply.resource_manager();
@@ -152,7 +464,7 @@ mod regulated_token {
                 // Update token's metadata to reflect the current stage
                 token_resource_manager.set_metadata(
                     "stage",
-                    "Stage 2 - Unlimited supply, may be restricted transfer".to_string(),
+                    Self::stage_description(&self.stage_descriptions, 2),
                 );
 
                 // Enable minting for the token
@@ -170,7 +482,7 @@ mod regulated_token {
                 // Update token's metadata to reflect the final stage
                 token_resource_manager.set_metadata(
                     "stage",
-                    "Stage 3 - Unregulated token, fixed supply".to_string(),
+                    Self::stage_description(&self.stage_descriptions, 3),
                 );
 
                 // Set our behavior appropriately now that the regulated period has ended
@@ -178,6 +490,8 @@ mod regulated_token {
                 token_resource_manager.set_freezeable(rule!(deny_all));
                 token_resource_manager.set_recallable(rule!(deny_all));
                 token_resource_manager.set_withdrawable(rule!(allow_all));
+                self.transfers_frozen = false;
+                self.pending_freeze_change = None;
                 token_resource_manager.set_metadata_role("metadata_setter", rule!(deny_all));
                 token_resource_manager
                     .set_metadata_role("metadata_setter_updater", rule!(deny_all));
@@ -199,43 +513,218 @@ mod regulated_token {
 
         /// Buy a quantity of tokens, if the supply on-hand is sufficient, or if current rules permit minting additional supply.
         /// The system will *always* allow buyers to purchase available tokens, even when the token transfers are otherwise frozen
-        pub fn buy_token(&mut self, quantity: Decimal, mut payment: Bucket) -> (Bucket, Bucket) {
+        /// If receipts are enabled, a purchase receipt is returned along with the tokens and the change
+        /// If a compliance hook is set, the purchase is reported to it and reverts if the hook vetoes it
+        /// If a per-epoch sale limit is set, purchases beyond what remains of it this epoch are rejected
+        /// The `buyer` account is only required if receipts or a compliance hook are enabled, to attribute the purchase to
+        /// If given, the owner of the `buyer` account must be present, so purchases can't be attributed to someone else
+        pub fn buy_token(
+            &mut self,
+            quantity: Decimal,
+            mut payment: Bucket,
+            buyer: Option<ComponentAddress>,
+        ) -> (Bucket, Bucket, Option<Bucket>) {
             assert!(
                 quantity > dec!("0"),
                 "Can't sell you nothing or less than nothing"
             );
+            if let Some(buyer) = buyer {
+                assert_account_owner(buyer);
+            }
+            assert_eq!(
+                payment.resource_address(),
+                self.collected_xrd.resource_address(),
+                "Wrong payment resource: expected {:?} but got {:?}",
+                self.collected_xrd.resource_address(),
+                payment.resource_address()
+            );
+            self.record_epoch_sale(quantity);
 
-            // Early birds who buy during stage 1 get a discounted rate
-            let price: Decimal = if self.current_stage == 1 {
-                dec!("50")
-            } else {
-                dec!("100")
-            };
-
-            // Take what we're owed
-            self.collected_xrd
-                .put(payment.take(price.checked_mul(quantity).unwrap()));
+            let price: Decimal = self.current_price();
+            let cost = price.checked_mul(quantity).unwrap();
+            assert!(
+                payment.amount() >= cost,
+                "Insufficient payment: {} tokens cost {} XRD but only {} XRD was provided",
+                quantity,
+                cost,
+                payment.amount()
+            );
 
             // Can we fill the desired quantity from current supply?
             let extra_demand = quantity.checked_sub(self.token_supply.amount()).unwrap();
+            assert!(
+                extra_demand <= dec!("0") || self.current_stage == 2,
+                "{}: can't sell {} tokens because only {} remain in supply and minting is only allowed in stage 2",
+                Self::stage_name(self.current_stage),
+                quantity,
+                self.token_supply.amount()
+            );
+
+            // Take what we're owed, and route it to the treasury right away if instant payout is configured
+            let owed = payment.take(cost);
+            match self.instant_payout {
+                Some(instant_payout) => {
+                    let mut treasury: Global<Account> = Global::from(instant_payout);
+                    treasury.try_deposit_or_abort(owed, None);
+                }
+                None => self.collected_xrd.put(owed),
+            }
+            let receipt = self.issue_receipt(buyer, quantity, price);
+
             if extra_demand <= dec!("0") {
                 // Take the required quantity, and return it along with any change
                 // The token may currently be under restricted transfer, so we will authorize our withdrawal
                 let tokens = self.token_supply.take(quantity);
+                self.report_sale(buyer, quantity, price);
 
-                return (tokens, payment);
+                return (tokens, payment, receipt);
             } else {
-                // We will attempt to mint the shortfall
-                // If we are in stage 1 or 3, this action will fail, and it would probably be a good idea to tell the user this
-                // For the purposes of example, we will blindly attempt to mint
+                // We are in stage 2 (checked above), so we may mint the shortfall as long as the cap allows it
+                if let Some(remaining) = self.remaining_stage2_mint() {
+                    assert!(extra_demand <= remaining, "stage 2 mint cap reached");
+                }
                 let mut tokens = self.token_supply.resource_manager().mint(extra_demand);
+                self.stage2_minted = self.stage2_minted.checked_add(extra_demand).unwrap();
+                Runtime::emit_event(SupplyMintedEvent {
+                    amount: extra_demand,
+                    total_minted: self.stage2_minted,
+                });
 
                 // Combine the new tokens with whatever was left in supply to meet the full quantity
                 let existing_tokens = self.token_supply.take_all();
                 tokens.put(existing_tokens);
+                self.report_sale(buyer, quantity, price);
 
                 // Return the tokens, along with any change
-                return (tokens, payment);
+                return (tokens, payment, receipt);
+            }
+        }
+
+        /// Freezes or unfreezes consumer transfers of the supply right away
+        fn set_transfer_freeze(&mut self, set_frozen: bool) {
+            // Note that this operation will fail if the token has reached stage 3 and the token behavior has been locked
+            let token_resource_manager = self.token_supply.resource_manager();
+            self.transfers_frozen = set_frozen;
+
+            if set_frozen {
+                token_resource_manager.set_withdrawable(Self::frozen_withdraw_rule(
+                    self.freeze_admin_badge_address,
+                    Runtime::global_address(),
+                    &self.freeze_exempt,
+                ));
+                info!("Token transfer is now RESTRICTED");
+            } else {
+                token_resource_manager.set_withdrawable(rule!(allow_all));
+                info!("Token is now freely transferrable");
+            }
+        }
+
+        /// Reapplies the withdraw rule after the exempt addresses changed, if transfers are currently frozen
+        fn update_frozen_withdraw_rule(&self) {
+            if self.transfers_frozen {
+                self.token_supply
+                    .resource_manager()
+                    .set_withdrawable(Self::frozen_withdraw_rule(
+                        self.freeze_admin_badge_address,
+                        Runtime::global_address(),
+                        &self.freeze_exempt,
+                    ));
+            }
+        }
+
+        /// The withdraw rule of the token while transfers are frozen: the freeze admin badge, our
+        /// component, or any of the exempt addresses may still move tokens
+        fn frozen_withdraw_rule(
+            freeze_admin_badge_address: ResourceAddress,
+            component_address: ComponentAddress,
+            freeze_exempt: &HashSet<ComponentAddress>,
+        ) -> AccessRule {
+            let mut allowed: Vec<ResourceOrNonFungible> = vec![
+                ResourceOrNonFungible::Resource(freeze_admin_badge_address),
+                global_caller(component_address),
+            ];
+            allowed.extend(freeze_exempt.iter().map(|address| global_caller(*address)));
+            rule!(require_any_of(allowed))
+        }
+
+        /// Counts a purchase towards the per-epoch sale limit, starting a new count when the epoch
+        /// changes, and rejects it if it exceeds what remains of the limit this epoch
+        fn record_epoch_sale(&mut self, quantity: Decimal) {
+            let remaining = self.remaining_this_epoch();
+            assert!(
+                quantity <= remaining,
+                "Per-epoch sale limit reached: only {} tokens may still be bought this epoch",
+                remaining
+            );
+
+            let current_epoch = Runtime::current_epoch();
+            if current_epoch != self.sold_epoch {
+                self.sold_epoch = current_epoch;
+                self.sold_this_epoch = dec!("0");
+            }
+            self.sold_this_epoch = self.sold_this_epoch.checked_add(quantity).unwrap();
+        }
+
+        /// Reports a purchase to the compliance hook, if one is set. A panic in the hook reverts the
+        /// whole purchase.
+        fn report_sale(&self, buyer: Option<ComponentAddress>, quantity: Decimal, price: Decimal) {
+            if let Some(compliance_hook) = self.compliance_hook {
+                let buyer = buyer.expect("A buyer account is required to report the sale");
+                let compliance_hook: Global<AnyComponent> = Global::from(compliance_hook);
+                compliance_hook
+                    .call_raw::<()>("report_sale", scrypto_args!(buyer, quantity, price));
+            }
+        }
+
+        /// Mints a receipt for a purchase, if receipts are enabled
+        fn issue_receipt(
+            &self,
+            buyer: Option<ComponentAddress>,
+            quantity: Decimal,
+            price: Decimal,
+        ) -> Option<Bucket> {
+            self.receipt_resource_address
+                .map(|receipt_resource_address| {
+                    ResourceManager::from(receipt_resource_address).mint_ruid_non_fungible(
+                        PurchaseReceipt {
+                            buyer: buyer.expect("A buyer account is required to issue a receipt"),
+                            quantity,
+                            price,
+                            epoch: Runtime::current_epoch(),
+                        },
+                    )
+                })
+        }
+
+        /// The description of a stage shown in the token's "stage" metadata, falling back to the
+        /// default stage name if the issuer did not provide one
+        fn stage_description(stage_descriptions: &[String], stage: u8) -> String {
+            stage_descriptions
+                .get(usize::from(stage) - 1)
+                .cloned()
+                .unwrap_or_else(|| Self::stage_name(stage).to_string())
+        }
+
+        /// The default human readable name of a stage
+        fn stage_name(stage: u8) -> &'static str {
+            match stage {
+                1 => "Stage 1 - Fixed supply, may be restricted transfer",
+                2 => "Stage 2 - Unlimited supply, may be restricted transfer",
+                _ => "Stage 3 - Unregulated token, fixed supply",
+            }
+        }
+
+        /// Early birds who buy during stage 1 get a discounted rate, as long as the discount lasts
+        fn current_price(&self) -> Decimal {
+            let discount_active = match self.discount_until {
+                Some(discount_until) => Runtime::current_epoch().number() < discount_until,
+                None => true,
+            };
+
+            if self.current_stage == 1 && discount_active {
+                dec!("50")
+            } else {
+                dec!("100")
             }
         }
     }
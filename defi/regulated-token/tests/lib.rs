@@ -0,0 +1,1070 @@
+use radix_engine::transaction::TransactionReceipt;
+use regulated_token::{ComponentInfo, PurchaseReceipt, SaleInfo};
+use scrypto::prelude::*;
+use scrypto_unit::*;
+use transaction::{builder::ManifestBuilder, prelude::TransactionManifestV1};
+
+/// The package of the mock components, which is published next to this package where a test needs them.
+const TEST_MOCKS_PACKAGE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../shared/test-mocks");
+
+/// The optional features of a sale, mirroring the `SaleConfig` of the blueprint.
+#[derive(ManifestSbor)]
+pub struct SaleConfig {
+    discount_until: Option<u64>,
+    stage2_mint_cap: Option<Decimal>,
+    issue_receipts: bool,
+    instant_payout: Option<ComponentAddress>,
+    dust_threshold: Decimal,
+    compliance_hook: Option<ComponentAddress>,
+    stage_descriptions: Vec<String>,
+    max_sold_per_epoch: Option<Decimal>,
+    freeze_change_notice_epochs: u64,
+}
+
+pub struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    public_key: Secp256k1PublicKey,
+    account_address: ComponentAddress,
+    package_address: PackageAddress,
+    component_address: ComponentAddress,
+    admin_badge: ResourceAddress,
+    freeze_admin_badge: ResourceAddress,
+    token: ResourceAddress,
+    receipt_resource: Option<ResourceAddress>,
+    treasury: Option<ComponentAddress>,
+    compliance_hook: Option<ComponentAddress>,
+}
+
+/// The arguments used to instantiate the sale, with the test-only parts of its `SaleConfig`
+/// replaced by the switches which set them up.
+pub struct SaleArguments {
+    discount_until: Option<u64>,
+    stage2_mint_cap: Option<Decimal>,
+    issue_receipts: bool,
+    /// Pays every purchase out to a new treasury account.
+    instant_payout: bool,
+    dust_threshold: Decimal,
+    /// Reports every purchase to a new mock compliance component, which vetoes all purchases if set.
+    compliance_veto: Option<bool>,
+    stage_descriptions: Vec<String>,
+    max_sold_per_epoch: Option<Decimal>,
+    freeze_change_notice_epochs: u64,
+}
+
+impl Default for SaleArguments {
+    fn default() -> Self {
+        Self {
+            discount_until: None,
+            stage2_mint_cap: None,
+            issue_receipts: false,
+            instant_payout: false,
+            dust_threshold: dec!("0"),
+            compliance_veto: None,
+            stage_descriptions: Vec::new(),
+            max_sold_per_epoch: None,
+            freeze_change_notice_epochs: 0,
+        }
+    }
+}
+
+impl TestEnvironment {
+    pub fn instantiate_test() -> Self {
+        Self::instantiate_test_with(SaleArguments::default())
+    }
+
+    /// Instantiates the sale with the given arguments.
+    pub fn instantiate_test_with(arguments: SaleArguments) -> Self {
+        let SaleArguments {
+            discount_until,
+            stage2_mint_cap,
+            issue_receipts,
+            instant_payout,
+            dust_threshold,
+            compliance_veto,
+            stage_descriptions,
+            max_sold_per_epoch,
+            freeze_change_notice_epochs,
+        } = arguments;
+        let mut test_runner = TestRunnerBuilder::new().build();
+
+        // Create an account
+        let (public_key, _private_key, account_address) = test_runner.new_virtual_account();
+        let treasury = if instant_payout {
+            let (_, _, treasury) = test_runner.new_allocated_account();
+            Some(treasury)
+        } else {
+            None
+        };
+
+        // Publish package and instantiate the regulated token
+        let package_address = test_runner.compile_and_publish(this_package!());
+        let compliance_hook = compliance_veto.map(|veto| {
+            let mocks_package_address = test_runner.compile_and_publish(TEST_MOCKS_PACKAGE);
+            let manifest = ManifestBuilder::new()
+                .call_function(
+                    mocks_package_address,
+                    "MockCompliance",
+                    "instantiate_mock_compliance",
+                    manifest_args!(veto),
+                )
+                .build();
+            test_runner
+                .execute_manifest_ignoring_fee(manifest, vec![])
+                .expect_commit_success()
+                .new_component_addresses()[0]
+        });
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                package_address,
+                "RegulatedToken",
+                "instantiate_regulated_token",
+                manifest_args!(SaleConfig {
+                    discount_until,
+                    stage2_mint_cap,
+                    issue_receipts,
+                    instant_payout: treasury,
+                    dust_threshold,
+                    compliance_hook,
+                    stage_descriptions,
+                    max_sold_per_epoch,
+                    freeze_change_notice_epochs,
+                }),
+            )
+            .deposit_batch(account_address)
+            .build();
+        let receipt = test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        );
+        let commit = receipt.expect_commit_success();
+        let component_address = commit.new_component_addresses()[0];
+        let admin_badge = commit.new_resource_addresses()[0];
+        let freeze_admin_badge = commit.new_resource_addresses()[1];
+        let token = commit.new_resource_addresses()[2];
+        let receipt_resource = commit.new_resource_addresses().get(3).copied();
+
+        Self {
+            test_runner,
+            public_key,
+            account_address,
+            package_address,
+            component_address,
+            admin_badge,
+            freeze_admin_badge,
+            token,
+            receipt_resource,
+            treasury,
+            compliance_hook,
+        }
+    }
+
+    pub fn execute(&mut self, manifest: TransactionManifestV1) -> TransactionReceipt {
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    pub fn buy_token(&mut self, quantity: Decimal, payment: Decimal) -> TransactionReceipt {
+        self.buy_token_with(XRD, quantity, payment)
+    }
+
+    /// Buys tokens paying with the given resource, which need not be the one the sale accepts.
+    pub fn buy_token_with(
+        &mut self,
+        payment_resource: ResourceAddress,
+        quantity: Decimal,
+        payment: Decimal,
+    ) -> TransactionReceipt {
+        let buyer = self.account_address;
+        self.buy_token_for(Some(buyer), payment_resource, quantity, payment)
+    }
+
+    /// Buys tokens on behalf of the given buyer account, if any, paying from the test account.
+    pub fn buy_token_for(
+        &mut self,
+        buyer: Option<ComponentAddress>,
+        payment_resource: ResourceAddress,
+        quantity: Decimal,
+        payment: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account_address, payment_resource, payment)
+            .take_all_from_worktop(payment_resource, "payment")
+            .call_method_with_name_lookup(self.component_address, "buy_token", |lookup| {
+                (quantity, lookup.bucket("payment"), buyer)
+            })
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn collect_payments(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.admin_badge, dec!("1"))
+            .call_method(self.component_address, "collect_payments", manifest_args!())
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn sweep_residuals(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.admin_badge, dec!("1"))
+            .call_method(self.component_address, "sweep_residuals", manifest_args!())
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn advance_stage(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.admin_badge, dec!("1"))
+            .call_method(self.component_address, "advance_stage", manifest_args!())
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn toggle_transfer_freeze(&mut self, set_frozen: bool) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(
+                self.account_address,
+                self.freeze_admin_badge,
+                dec!("1"),
+            )
+            .call_method(
+                self.component_address,
+                "toggle_transfer_freeze",
+                manifest_args!(set_frozen),
+            )
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn apply_pending_freeze_change(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "apply_pending_freeze_change",
+                manifest_args!(),
+            )
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn cancel_pending_freeze_change(&mut self) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(
+                self.account_address,
+                self.freeze_admin_badge,
+                dec!("1"),
+            )
+            .call_method(
+                self.component_address,
+                "cancel_pending_freeze_change",
+                manifest_args!(),
+            )
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn add_exempt(&mut self, address: ComponentAddress) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.admin_badge, dec!("1"))
+            .call_method(
+                self.component_address,
+                "add_exempt",
+                manifest_args!(address),
+            )
+            .build();
+
+        self.execute(manifest)
+    }
+
+    /// Transfers `amount` of the token from the `from` account, signed by `public_key`, to the `to` account.
+    pub fn transfer_token(
+        &mut self,
+        public_key: Secp256k1PublicKey,
+        from: ComponentAddress,
+        to: ComponentAddress,
+        amount: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(from, self.token, amount)
+            .try_deposit_batch_or_abort(to, None)
+            .build();
+
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+    }
+
+    /// Instantiates a mock DEX holding 1000 of a new token which it pays out at the given `rate`.
+    /// Returns the addresses of the DEX and of its output token.
+    pub fn instantiate_mock_dex(&mut self, rate: Decimal) -> (ComponentAddress, ResourceAddress) {
+        let output_token =
+            self.test_runner
+                .create_fungible_resource(dec!("1000"), 18, self.account_address);
+        let mocks_package_address = self.test_runner.compile_and_publish(TEST_MOCKS_PACKAGE);
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account_address, output_token, dec!("1000"))
+            .take_all_from_worktop(output_token, "output_tokens")
+            .call_function_with_name_lookup(
+                mocks_package_address,
+                "MockDex",
+                "instantiate_mock_dex",
+                |lookup| (lookup.bucket("output_tokens"), rate),
+            )
+            .build();
+        let dex = self
+            .execute(manifest)
+            .expect_commit_success()
+            .new_component_addresses()[0];
+
+        (dex, output_token)
+    }
+
+    pub fn swap_collected(
+        &mut self,
+        dex: ComponentAddress,
+        min_out: Decimal,
+        amount_in: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account_address, self.admin_badge, dec!("1"))
+            .call_method(
+                self.component_address,
+                "swap_collected",
+                manifest_args!(dex, min_out, amount_in),
+            )
+            .deposit_batch(self.account_address)
+            .build();
+
+        self.execute(manifest)
+    }
+
+    pub fn remaining_stage2_mint(&mut self) -> Option<Decimal> {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "remaining_stage2_mint",
+                manifest_args!(),
+            )
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+
+    pub fn remaining_this_epoch(&mut self) -> Decimal {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "remaining_this_epoch",
+                manifest_args!(),
+            )
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+
+    pub fn get_component_info(&mut self) -> ComponentInfo {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                "get_component_info",
+                manifest_args!(),
+            )
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+
+    /// The `(buyer, quantity, price)` of every sale reported to the mock compliance hook
+    pub fn reported_sales(&mut self) -> Vec<(ComponentAddress, Decimal, Decimal)> {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.compliance_hook.unwrap(),
+                "reported_sales",
+                manifest_args!(),
+            )
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+
+    /// The token's "stage" metadata
+    pub fn stage_metadata(&mut self) -> Option<String> {
+        match self.test_runner.get_metadata(self.token.into(), "stage") {
+            Some(MetadataValue::String(stage)) => Some(stage),
+            _ => None,
+        }
+    }
+
+    pub fn get_sale_info(&mut self) -> SaleInfo {
+        let manifest = ManifestBuilder::new()
+            .call_method(self.component_address, "get_sale_info", manifest_args!())
+            .build();
+
+        self.execute(manifest).expect_commit_success().output(1)
+    }
+}
+
+#[test]
+fn stage_one_discount_applies_before_discount_epoch() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        discount_until: Some(10),
+        ..Default::default()
+    });
+    test_environment.test_runner.set_current_epoch(Epoch::of(5));
+
+    assert_eq!(test_environment.get_sale_info().current_price, dec!("50"));
+
+    let xrd_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+    test_environment
+        .buy_token(dec!("1"), dec!("100"))
+        .expect_commit_success();
+    let xrd_after = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+
+    assert_eq!(xrd_before.checked_sub(xrd_after).unwrap(), dec!("50"));
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, test_environment.token),
+        dec!("1")
+    );
+}
+
+#[test]
+fn stage_one_discount_expires_at_discount_epoch() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        discount_until: Some(10),
+        ..Default::default()
+    });
+    test_environment
+        .test_runner
+        .set_current_epoch(Epoch::of(10));
+
+    let sale_info = test_environment.get_sale_info();
+    assert_eq!(sale_info.current_stage, 1);
+    assert_eq!(sale_info.current_price, dec!("100"));
+    assert_eq!(sale_info.discount_until, Some(10));
+
+    let xrd_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+    test_environment
+        .buy_token(dec!("1"), dec!("100"))
+        .expect_commit_success();
+    let xrd_after = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+
+    assert_eq!(xrd_before.checked_sub(xrd_after).unwrap(), dec!("100"));
+}
+
+#[test]
+fn stage_one_discount_without_expiry_never_ends() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .test_runner
+        .set_current_epoch(Epoch::of(1_000));
+
+    assert_eq!(test_environment.get_sale_info().current_price, dec!("50"));
+}
+
+#[test]
+fn paying_with_the_wrong_resource_names_both_resources() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let other_token = test_environment.test_runner.create_fungible_resource(
+        dec!("1000"),
+        18,
+        test_environment.account_address,
+    );
+
+    let receipt = test_environment.buy_token_with(other_token, dec!("1"), dec!("100"));
+
+    let expected_message = format!(
+        "Wrong payment resource: expected {:?} but got {:?}",
+        XRD, other_token
+    );
+    receipt.expect_specific_failure(|error| format!("{:?}", error).contains(&expected_message));
+}
+
+#[test]
+fn insufficient_payment_reports_the_cost() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let receipt = test_environment.buy_token(dec!("2"), dec!("60"));
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error)
+            .contains("Insufficient payment: 2 tokens cost 100 XRD but only 60 XRD was provided")
+    });
+}
+
+#[test]
+fn buying_beyond_the_supply_in_stage_one_names_the_stage() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let receipt = test_environment.buy_token(dec!("101"), dec!("5050"));
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains(
+            "Stage 1 - Fixed supply, may be restricted transfer: can't sell 101 tokens because \
+             only 100 remain in supply and minting is only allowed in stage 2",
+        )
+    });
+}
+
+#[test]
+fn stage_two_minting_is_allowed_up_to_the_cap() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        stage2_mint_cap: Some(dec!("40")),
+        ..Default::default()
+    });
+    test_environment
+        .buy_token(dec!("100"), dec!("5000"))
+        .expect_commit_success();
+    test_environment.advance_stage().expect_commit_success();
+    assert_eq!(test_environment.remaining_stage2_mint(), Some(dec!("40")));
+
+    test_environment
+        .buy_token(dec!("30"), dec!("3000"))
+        .expect_commit_success();
+    assert_eq!(test_environment.remaining_stage2_mint(), Some(dec!("10")));
+
+    test_environment
+        .buy_token(dec!("10"), dec!("1000"))
+        .expect_commit_success();
+    assert_eq!(test_environment.remaining_stage2_mint(), Some(dec!("0")));
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, test_environment.token),
+        dec!("140")
+    );
+}
+
+#[test]
+fn stage_two_minting_beyond_the_cap_fails() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        stage2_mint_cap: Some(dec!("40")),
+        ..Default::default()
+    });
+    test_environment
+        .buy_token(dec!("100"), dec!("5000"))
+        .expect_commit_success();
+    test_environment.advance_stage().expect_commit_success();
+
+    test_environment
+        .buy_token(dec!("41"), dec!("4100"))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("stage 2 mint cap reached")
+        });
+    assert_eq!(test_environment.remaining_stage2_mint(), Some(dec!("40")));
+}
+
+#[test]
+fn stage_two_minting_is_uncapped_by_default() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    assert_eq!(test_environment.remaining_stage2_mint(), None);
+}
+
+#[test]
+fn collected_xrd_can_be_swapped_through_a_dex() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .buy_token(dec!("2"), dec!("100"))
+        .expect_commit_success();
+    let (dex, output_token) = test_environment.instantiate_mock_dex(dec!("2"));
+
+    test_environment
+        .swap_collected(dex, dec!("120"), dec!("60"))
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, output_token),
+        dec!("120")
+    );
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.component_address, XRD),
+        dec!("40")
+    );
+}
+
+#[test]
+fn swap_with_too_little_output_fails_the_slippage_check() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .buy_token(dec!("2"), dec!("100"))
+        .expect_commit_success();
+    let (dex, _output_token) = test_environment.instantiate_mock_dex(dec!("2"));
+
+    test_environment
+        .swap_collected(dex, dec!("121"), dec!("60"))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error)
+                .contains("Slippage too high: the swap returned 120 but at least 121 was required")
+        });
+}
+
+#[test]
+fn component_info_reflects_the_state_before_and_after_stage_three() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .buy_token(dec!("10"), dec!("500"))
+        .expect_commit_success();
+
+    let component_info = test_environment.get_component_info();
+    assert_eq!(component_info.current_stage, 1);
+    assert_eq!(component_info.token_supply_amount, dec!("90"));
+    assert_eq!(component_info.collected_xrd, dec!("500"));
+    assert!(component_info.internal_authority_present);
+    assert_eq!(
+        component_info.admin_badge_address,
+        test_environment.admin_badge
+    );
+    assert_eq!(component_info.token_address, test_environment.token);
+
+    test_environment.advance_stage().expect_commit_success();
+    test_environment.advance_stage().expect_commit_success();
+
+    let component_info = test_environment.get_component_info();
+    assert_eq!(component_info.current_stage, 3);
+    assert_eq!(component_info.token_supply_amount, dec!("90"));
+    assert!(!component_info.internal_authority_present);
+}
+
+#[test]
+fn purchase_with_receipts_enabled_returns_a_receipt() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        issue_receipts: true,
+        ..Default::default()
+    });
+    let receipt_resource = test_environment.receipt_resource.unwrap();
+    test_environment.test_runner.set_current_epoch(Epoch::of(7));
+
+    test_environment
+        .buy_token(dec!("2"), dec!("100"))
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, receipt_resource),
+        dec!("1")
+    );
+    let vault = test_environment
+        .test_runner
+        .get_component_vaults(test_environment.account_address, receipt_resource)[0];
+    let (_, receipt_id) = test_environment
+        .test_runner
+        .inspect_non_fungible_vault(vault)
+        .unwrap();
+    let purchase_receipt: PurchaseReceipt = test_environment
+        .test_runner
+        .get_non_fungible_data(receipt_resource, receipt_id.unwrap());
+    assert_eq!(purchase_receipt.buyer, test_environment.account_address);
+    assert_eq!(purchase_receipt.quantity, dec!("2"));
+    assert_eq!(purchase_receipt.price, dec!("50"));
+    assert_eq!(purchase_receipt.epoch, Epoch::of(7));
+}
+
+#[test]
+fn purchase_on_behalf_of_another_account_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        issue_receipts: true,
+        ..Default::default()
+    });
+    let (_, _, other_account) = test_environment.test_runner.new_virtual_account();
+
+    test_environment
+        .buy_token_for(Some(other_account), XRD, dec!("2"), dec!("100"))
+        .expect_commit_failure();
+}
+
+#[test]
+fn purchase_without_a_buyer_is_allowed_unless_it_must_be_attributed() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    test_environment
+        .buy_token_for(None, XRD, dec!("2"), dec!("100"))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, test_environment.token),
+        dec!("2")
+    );
+
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        issue_receipts: true,
+        ..Default::default()
+    });
+    test_environment
+        .buy_token_for(None, XRD, dec!("2"), dec!("100"))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("A buyer account is required")
+        });
+
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        compliance_veto: Some(false),
+        ..Default::default()
+    });
+    test_environment
+        .buy_token_for(None, XRD, dec!("2"), dec!("100"))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("A buyer account is required")
+        });
+}
+
+#[test]
+fn instant_payout_routes_every_payment_to_the_treasury() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        instant_payout: true,
+        ..Default::default()
+    });
+    let treasury = test_environment.treasury.unwrap();
+    let treasury_xrd = test_environment
+        .test_runner
+        .get_component_balance(treasury, XRD);
+
+    test_environment
+        .buy_token(dec!("2"), dec!("100"))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(treasury, XRD),
+        treasury_xrd.checked_add(dec!("100")).unwrap()
+    );
+
+    test_environment
+        .buy_token(dec!("1"), dec!("100"))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(treasury, XRD),
+        treasury_xrd.checked_add(dec!("150")).unwrap()
+    );
+    assert_eq!(
+        test_environment.get_component_info().collected_xrd,
+        dec!("0")
+    );
+}
+
+#[test]
+fn sweeping_returns_the_residuals_of_fractional_purchases() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        dust_threshold: dec!("0.001"),
+        ..Default::default()
+    });
+
+    // Nothing is swept while the supply is well above the dust threshold
+    test_environment.sweep_residuals().expect_commit_success();
+    assert_eq!(
+        test_environment.get_component_info().token_supply_amount,
+        dec!("100")
+    );
+
+    for _ in 0..3 {
+        test_environment
+            .buy_token(dec!("33.33333"), dec!("2000"))
+            .expect_commit_success();
+    }
+    test_environment.collect_payments().expect_commit_success();
+    let tokens_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, test_environment.token);
+
+    test_environment.sweep_residuals().expect_commit_success();
+
+    let tokens_after = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, test_environment.token);
+    assert_eq!(
+        tokens_after.checked_sub(tokens_before).unwrap(),
+        dec!("0.00001")
+    );
+    let component_info = test_environment.get_component_info();
+    assert_eq!(component_info.token_supply_amount, dec!("0"));
+    assert_eq!(component_info.collected_xrd, dec!("0"));
+}
+
+#[test]
+fn purchases_are_reported_to_the_compliance_hook() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        compliance_veto: Some(false),
+        ..Default::default()
+    });
+
+    test_environment
+        .buy_token(dec!("2"), dec!("100"))
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.reported_sales(),
+        vec![(test_environment.account_address, dec!("2"), dec!("50"))]
+    );
+}
+
+#[test]
+fn compliance_hook_veto_reverts_the_purchase() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        compliance_veto: Some(true),
+        ..Default::default()
+    });
+    let xrd_before = test_environment
+        .test_runner
+        .get_component_balance(test_environment.account_address, XRD);
+
+    test_environment
+        .buy_token(dec!("2"), dec!("100"))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("Sale vetoed by compliance")
+        });
+
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, XRD),
+        xrd_before
+    );
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, test_environment.token),
+        dec!("0")
+    );
+    assert_eq!(test_environment.reported_sales(), vec![]);
+}
+
+#[test]
+fn stage_metadata_uses_the_configured_descriptions() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        stage_descriptions: vec!["Presale".to_string(), "Open sale".to_string()],
+        ..Default::default()
+    });
+    assert_eq!(
+        test_environment.stage_metadata(),
+        Some("Presale".to_string())
+    );
+
+    test_environment.advance_stage().expect_commit_success();
+    assert_eq!(
+        test_environment.stage_metadata(),
+        Some("Open sale".to_string())
+    );
+
+    // There is no description for stage 3, so the default is used
+    test_environment.advance_stage().expect_commit_success();
+    assert_eq!(
+        test_environment.stage_metadata(),
+        Some("Stage 3 - Unregulated token, fixed supply".to_string())
+    );
+}
+
+#[test]
+fn purchases_are_limited_per_epoch() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        max_sold_per_epoch: Some(dec!("5")),
+        ..Default::default()
+    });
+
+    test_environment
+        .buy_token(dec!("3"), dec!("150"))
+        .expect_commit_success();
+    test_environment
+        .buy_token(dec!("2"), dec!("100"))
+        .expect_commit_success();
+    assert_eq!(test_environment.remaining_this_epoch(), dec!("0"));
+
+    test_environment
+        .buy_token(dec!("1"), dec!("50"))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains(
+                "Per-epoch sale limit reached: only 0 tokens may still be bought this epoch",
+            )
+        });
+
+    test_environment.test_runner.set_current_epoch(Epoch::of(5));
+    assert_eq!(test_environment.remaining_this_epoch(), dec!("5"));
+
+    test_environment
+        .buy_token(dec!("1"), dec!("50"))
+        .expect_commit_success();
+    assert_eq!(test_environment.remaining_this_epoch(), dec!("4"));
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(test_environment.account_address, test_environment.token),
+        dec!("6")
+    );
+}
+
+#[test]
+fn freeze_exempt_addresses_can_still_move_tokens() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (custodian_key, _, custodian) = test_environment.test_runner.new_allocated_account();
+    let (holder_key, holder) = (
+        test_environment.public_key,
+        test_environment.account_address,
+    );
+
+    test_environment
+        .buy_token(dec!("5"), dec!("500"))
+        .expect_commit_success();
+    test_environment
+        .toggle_transfer_freeze(false)
+        .expect_commit_success();
+    test_environment
+        .transfer_token(holder_key, holder, custodian, dec!("2"))
+        .expect_commit_success();
+
+    test_environment
+        .toggle_transfer_freeze(true)
+        .expect_commit_success();
+    test_environment
+        .add_exempt(custodian)
+        .expect_commit_success();
+
+    // A normal holder can't move their tokens while transfers are frozen, but the exempt custodian can
+    test_environment
+        .transfer_token(holder_key, holder, custodian, dec!("1"))
+        .expect_commit_failure();
+    test_environment
+        .transfer_token(custodian_key, custodian, holder, dec!("1"))
+        .expect_commit_success();
+
+    let token = test_environment.token;
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(holder, token),
+        dec!("4")
+    );
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(custodian, token),
+        dec!("1")
+    );
+}
+
+#[test]
+fn scheduled_freeze_change_only_takes_effect_after_the_notice() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        freeze_change_notice_epochs: 10,
+        ..Default::default()
+    });
+    let (_, _, recipient) = test_environment.test_runner.new_allocated_account();
+    let (holder_key, holder) = (
+        test_environment.public_key,
+        test_environment.account_address,
+    );
+    test_environment
+        .buy_token(dec!("5"), dec!("500"))
+        .expect_commit_success();
+
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+    test_environment
+        .toggle_transfer_freeze(false)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.get_sale_info().pending_freeze_change,
+        Some((false, current_epoch.number() + 10))
+    );
+
+    // Transfers stay frozen until the notice period has passed and the change has been applied
+    test_environment
+        .transfer_token(holder_key, holder, recipient, dec!("1"))
+        .expect_commit_failure();
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(9).unwrap());
+    test_environment
+        .apply_pending_freeze_change()
+        .expect_specific_failure(|error| format!("{:?}", error).contains("only takes effect"));
+
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(10).unwrap());
+    test_environment
+        .apply_pending_freeze_change()
+        .expect_commit_success();
+    assert_eq!(test_environment.get_sale_info().pending_freeze_change, None);
+    test_environment
+        .transfer_token(holder_key, holder, recipient, dec!("1"))
+        .expect_commit_success();
+}
+
+#[test]
+fn cancelled_freeze_change_is_never_applied() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        freeze_change_notice_epochs: 10,
+        ..Default::default()
+    });
+    let (_, _, recipient) = test_environment.test_runner.new_allocated_account();
+    let (holder_key, holder) = (
+        test_environment.public_key,
+        test_environment.account_address,
+    );
+    test_environment
+        .buy_token(dec!("5"), dec!("500"))
+        .expect_commit_success();
+
+    let current_epoch = test_environment.test_runner.get_current_epoch();
+    test_environment
+        .toggle_transfer_freeze(false)
+        .expect_commit_success();
+    test_environment
+        .cancel_pending_freeze_change()
+        .expect_commit_success();
+    assert_eq!(test_environment.get_sale_info().pending_freeze_change, None);
+
+    test_environment
+        .test_runner
+        .set_current_epoch(current_epoch.after(10).unwrap());
+    test_environment
+        .apply_pending_freeze_change()
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("No freeze change is scheduled")
+        });
+    test_environment
+        .transfer_token(holder_key, holder, recipient, dec!("1"))
+        .expect_commit_failure();
+}
+
+#[test]
+fn freeze_change_can_not_be_scheduled_in_the_final_stage() {
+    let mut test_environment = TestEnvironment::instantiate_test_with(SaleArguments {
+        freeze_change_notice_epochs: 10,
+        ..Default::default()
+    });
+    test_environment.advance_stage().expect_commit_success();
+    test_environment.advance_stage().expect_commit_success();
+
+    test_environment
+        .toggle_transfer_freeze(true)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("can't be changed in the final stage")
+        });
+    assert_eq!(test_environment.get_sale_info().pending_freeze_change, None);
+}
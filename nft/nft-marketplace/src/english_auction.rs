@@ -1,19 +1,62 @@
+use account_auth::account_owner_rule;
 use scrypto::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// The maximum number of bids kept in the bid history. Once reached, the oldest bids are dropped.
+const MAX_BID_HISTORY_LENGTH: usize = 50;
+
+/// The number of epochs after the ending epoch of the auction after which the seller may rescue anything still held in
+/// escrow through `emergency_withdraw`.
+const RESCUE_DELAY_EPOCHS: u64 = 10_000;
+
+/// The maximum lengths of the listing metadata strings, in bytes.
+const MAX_TITLE_LENGTH: usize = 100;
+const MAX_DESCRIPTION_LENGTH: usize = 1_000;
+const MAX_IMAGE_URL_LENGTH: usize = 500;
+
+/// A bid placed within this many epochs of the ending epoch extends the auction so that it ends this many epochs after
+/// the bid, giving other bidders a chance to respond to last-second bids.
+const EXTENSION_WINDOW_EPOCHS: u64 = 5;
 
 #[blueprint]
+#[events(AuctionExtendedEvent)]
 mod english_auction {
     // Setting up the access rules for the component methods such that only the owner of the ownership badge can
     // make calls to the protected methods.
     enable_method_auth! {
+        roles {
+            dispute_arbiter => updatable_by: [];
+            fee_collector => updatable_by: [];
+        },
         methods {
             cancel_auction => restrict_to: [OWNER];
             withdraw_payment => restrict_to: [OWNER];
+            withdraw_fees => restrict_to: [fee_collector];
+            emergency_withdraw => restrict_to: [OWNER];
+            set_beneficiary => restrict_to: [OWNER];
+            reveal_reserve => restrict_to: [OWNER];
+            deposit_lot => restrict_to: [OWNER];
+            withdraw_unsold_lot => restrict_to: [OWNER];
+            resolve_dispute => restrict_to: [dispute_arbiter];
             bid => PUBLIC;
+            bid_with_refund_account => PUBLIC;
+            place_proxy_bid => PUBLIC;
+            bid_for_quantity => PUBLIC;
             increase_bid => PUBLIC;
             cancel_bid => PUBLIC;
             claim_nfts => PUBLIC;
+            claim_fill => PUBLIC;
             ensure_auction_settlement => PUBLIC;
+            settle => PUBLIC;
+            raise_dispute => PUBLIC;
             has_bids => PUBLIC;
+            bid_history => PUBLIC;
+            min_next_bid => PUBLIC;
+            get_listing_metadata => PUBLIC;
+            extension_count => PUBLIC;
+            starting_price => PUBLIC;
+            outcome => PUBLIC;
+            fill_amount => PUBLIC;
         }
     }
     /// This blueprint defines the state and logic involved in a english auction non-fungible token sale. People who
@@ -33,10 +76,76 @@ mod english_auction {
         /// the same time. This lazymaps maps the bidder's badge to a vault which contains the funds that they bid.
         bid_vaults: HashMap<NonFungibleLocalId, Vault>,
 
+        /// Bidders may place proxy bids where they escrow a maximum amount and the auction automatically bids on
+        /// their behalf, only as much as is needed to stay ahead of the competing bids. This maps the bidder's badge
+        /// of every proxy bid to its maximum and its current effective bid.
+        proxy_bids: HashMap<NonFungibleLocalId, ProxyBid>,
+
+        /// Whether the auction sells a divisible fungible lot to multiple winners instead of an NFT bundle to a single
+        /// winner. The lot is allocated to the bids with the highest price per unit first, and among bids of the same
+        /// price to the earliest one, until it is exhausted.
+        multi_fill: bool,
+
+        /// The fungible lot sold in a multi-fill auction, which is `None` until the seller deposits it.
+        lot_vault: Option<Vault>,
+
+        /// The quantity of the lot that every bidder of a multi-fill auction bid for.
+        tranche_bids: HashMap<NonFungibleLocalId, TrancheBid>,
+
+        /// The number of tranche bids placed so far, which is used to order bids of the same price by time.
+        tranche_bid_count: u64,
+
+        /// The quantity of the lot allocated to every winning bidder of a multi-fill auction when it settled.
+        fills: HashMap<NonFungibleLocalId, Decimal>,
+
+        /// The part of the allocated quantity of the lot which the winners have not claimed yet.
+        unclaimed_fills: Decimal,
+
+        /// The accounts which bidders asked their funds to be refunded to when they are outbid. Refunds are only pushed
+        /// to these accounts if `push_refunds` is set.
+        refund_accounts: HashMap<NonFungibleLocalId, ComponentAddress>,
+
+        /// Whether the escrowed funds of outbid bidders are pushed to their refund accounts as soon as they are outbid.
+        /// If this is not set, or if a refund account rejects the deposit, outbid bidders withdraw their funds through
+        /// `cancel_bid` instead.
+        push_refunds: bool,
+
+        /// The most recent bids made in the auction, oldest first. This is capped at `MAX_BID_HISTORY_LENGTH` bids so
+        /// that the component state does not grow without bounds.
+        bid_history: Vec<BidRecord>,
+
         /// After the winner of the bid has been determined, their tokens will be sent to the payment vault which the
         /// seller has access to and can withdraw funds from.
         payment_vault: Vault,
 
+        /// The settlement fee taken out of the winning bid when the auction settles is stored in this vault, separately
+        /// from the seller's payment, until the holder of the fee collector badge withdraws it.
+        fee_vault: Vault,
+
+        /// The percentage of the winning bid which is kept as a settlement fee when the auction settles.
+        settlement_fee_percent: Decimal,
+
+        /// The number of epochs after the ending epoch within which the auction counts as settled early.
+        settlement_grace_epochs: u64,
+
+        /// The percentage of the settlement fee which is rebated to the seller if the auction is settled early.
+        early_settlement_rebate_percent: Decimal,
+
+        /// The amount of the winning bid which is paid to whoever settles the auction through `settle`, as an
+        /// incentive to settle auctions promptly. It is taken out of the seller's proceeds.
+        settlement_reward: Decimal,
+
+        /// The account which settled an auction with a dispute window through `settle`. Its settlement reward is held
+        /// in the payment vault until the proceeds are released, so that a refunded winner gets their whole bid back.
+        settler_account: Option<ComponentAddress>,
+
+        /// How the seller's proceeds are converted into another token when they are withdrawn, if at all.
+        payout_conversion: Option<PayoutConversion>,
+
+        /// The account which receives the seller's proceeds and any unsold NFTs. When this is `None`, they are
+        /// returned to the holder of the ownership badge when they withdraw them.
+        beneficiary: Option<ComponentAddress>,
+
         /// When a bidder makes a bid, they're given a bidder's badge which proves that they've placed a bid in this
         /// component and with a proof of the amount of funds owed to them. If they wish to then cancel their bid or
         /// terminate it, they must present their bidder's badge to the appropriate methods on the an EnglishAuction
@@ -51,6 +160,53 @@ mod english_auction {
         /// if the minimum automatic sale price is reached, each parties will be given their tokens.
         ending_epoch: Epoch,
 
+        /// The maximum number of times that a late bid may extend the auction. Once this many extensions have taken
+        /// place, the ending epoch is firm regardless of late bids. Setting this to zero disables extensions.
+        max_extensions: u64,
+
+        /// The number of times that a late bid has extended the auction.
+        extension_count: u64,
+
+        /// The minimum amount of any bid. Unlike the minimum seller proceeds, this is known to bidders upfront.
+        starting_price: Decimal,
+
+        /// How much a new bid must exceed the highest bid by. Proxy bids are raised by the same increment above the
+        /// highest competing bid.
+        bid_increment: BidIncrement,
+
+        /// The minimum amount that the seller must receive from the winning bid. If the winning bid falls short of
+        /// this amount when the auction settles, the sale is voided: the NFTs go back to the seller and all of the
+        /// bidders, including the highest one, may withdraw their bids.
+        min_seller_proceeds: Decimal,
+
+        /// The SHA-256 hash of the SBOR encoded `(reserve, nonce)` tuple of the seller's hidden reserve, if any. The
+        /// commitment proves to bidders that the reserve was fixed before they bid.
+        reserve_commitment: Option<[u8; 32]>,
+
+        /// The hidden reserve once the seller has revealed it. A winning bid below the revealed reserve voids the sale,
+        /// while a reserve which is not revealed before the auction settles does not apply at all.
+        revealed_reserve: Option<Decimal>,
+
+        /// The human readable description of the auction provided by the seller.
+        listing_metadata: ListingMetadata,
+
+        /// The winning bid, which is set when the auction is settled.
+        winning_bid: Option<Decimal>,
+
+        /// The outcome of the auction, which is `Pending` until the auction is settled or canceled.
+        outcome: AuctionOutcome,
+
+        /// The number of epochs after settlement during which the winner may dispute the sale. The seller's proceeds
+        /// are held in escrow until this window has passed.
+        dispute_window_epochs: u64,
+
+        /// The epoch from which the seller may withdraw their proceeds, which is set when the auction is settled.
+        payout_release_epoch: Option<Epoch>,
+
+        /// Whether the winner has disputed the sale. While a dispute is pending, neither the proceeds nor the NFTs
+        /// may be withdrawn until the dispute arbiter resolves it.
+        disputed: bool,
+
         /// The English Auction is stateful and at different states of the auction different actions may or may not be
         /// possible.
         state: AuctionState,
@@ -72,6 +228,28 @@ mod english_auction {
         /// * **Check 1:** Checks that the passed buckets of tokens are all non-fungible tokens.
         /// * **Check 2:** Checks that the `accepted_payment_token` is a fungible token.
         /// * **Check 3:** Checks that the ending epoch has not yet passed.
+        /// * **Check 4:** Checks that the minimum seller proceeds are non-negative.
+        /// * **Check 5:** Checks that the settlement fee is between 0 and 100 percent.
+        /// * **Check 6:** Checks that the listing metadata strings do not exceed their maximum lengths.
+        /// * **Check 7:** Checks that the settlement reward is non-negative.
+        /// * **Check 8:** Checks that the starting price is non-negative.
+        /// * **Check 9:** Checks that the early settlement rebate is between 0 and 100 percent.
+        /// * **Check 10:** Checks that a dispute arbiter is given if there is a dispute window.
+        /// * **Check 11:** Checks that the payout token, if any, is a fungible token other than the payment token
+        /// and that the minimum payout is non-negative.
+        /// * **Check 12:** Checks that a multi-fill auction sells no NFTs and has no dispute window.
+        /// * **Check 13:** Checks that a fee collector is given if there is a settlement fee.
+        /// * **Check 14:** Checks that the bid increment is positive.
+        ///
+        /// The seller may hide a reserve by committing to it through `reserve_commitment` and revealing it through
+        /// `reveal_reserve` before the auction settles.
+        ///
+        /// A seller who records their account through `seller_account` gets no ownership badge. Instead, the seller
+        /// only methods are authorized by the owner of that account: the key a virtual account's address was derived
+        /// from, or the account's owner badge once it is securified.
+        ///
+        /// Bids placed within `EXTENSION_WINDOW_EPOCHS` of the ending epoch extend the auction, at most
+        /// `max_extensions` times.
         ///
         /// # Arguments:
         ///
@@ -79,23 +257,43 @@ mod english_auction {
         /// wishes to sell.
         /// * `accepted_payment_token` (ResourceAddress) - Payments may be accepted in XRD or non-XRD tokens. This
         /// argument specifies the resource address of the token the instantiator wishes to accept for payment.
-        /// * `starting_price` (Decimal) - The starting price of the NFT bundle sale.
-        /// * `ending_price` (Decimal) - The ending price of the NFT bundle sale.
         /// * `relative_ending_epoch` (u64) - This is the relative ending epoch, meaning that this value will be added
-        /// with the current epoch. This argument controls the rate at which the price of the bundle decreases. When
-        /// the ending epoch is reached, the price will reach its minimum that was specified in the arguments.
+        /// with the current epoch. When the ending epoch is reached, the auction is settled.
+        /// * `config` (AuctionConfig) - The optional features and the listing metadata of the auction. See
+        /// `AuctionConfig` for a description of each of them.
         ///
         /// # Returns:
         ///
         /// This function returns a tuple which has the following format:
         /// * `Global<EnglishAuction>` - A Global<EnglishAuction> component object of the instantiated `EnglishAuction` component.
-        /// * `Bucket` - A bucket containing an ownership badge which entitles the holder to the assets in this
-        /// component.
+        /// * `Option<Bucket>` - A bucket containing an ownership badge which entitles the holder to the assets in this
+        /// component, or `None` if the seller recorded their account instead.
         pub fn instantiate_english_auction(
             non_fungible_tokens: Vec<NonFungibleBucket>,
             accepted_payment_token: ResourceAddress,
             relative_ending_epoch: u64,
-        ) -> (Global<EnglishAuction>, FungibleBucket) {
+            config: AuctionConfig,
+        ) -> (Global<EnglishAuction>, Option<FungibleBucket>) {
+            let AuctionConfig {
+                min_seller_proceeds,
+                settlement_fee_percent,
+                fee_collector,
+                listing_metadata,
+                push_refunds,
+                max_extensions,
+                settlement_reward,
+                starting_price,
+                settlement_grace_epochs,
+                early_settlement_rebate_percent,
+                dispute_window_epochs,
+                dispute_arbiter,
+                payout_conversion,
+                multi_fill,
+                reserve_commitment,
+                seller_account,
+                bid_increment,
+            } = config;
+
             // Performing checks to ensure that the creation of the component can go through
             // assert!(
             //     !non_fungible_tokens.iter().any(|x| !matches!(
@@ -115,6 +313,79 @@ mod english_auction {
                 Runtime::current_epoch().after(relative_ending_epoch).unwrap() > Runtime::current_epoch(),
                 "[Instantiation]: The ending epoch has already passed."
             );
+            assert!(
+                min_seller_proceeds >= Decimal::zero(),
+                "[Instantiation]: The minimum seller proceeds can not be negative."
+            );
+            assert!(
+                settlement_fee_percent >= Decimal::zero() && settlement_fee_percent <= dec!(100),
+                "[Instantiation]: The settlement fee must be between 0 and 100 percent."
+            );
+            assert!(
+                listing_metadata.title.len() <= MAX_TITLE_LENGTH,
+                "[Instantiation]: The title can not be longer than {} bytes.",
+                MAX_TITLE_LENGTH
+            );
+            assert!(
+                listing_metadata.description.len() <= MAX_DESCRIPTION_LENGTH,
+                "[Instantiation]: The description can not be longer than {} bytes.",
+                MAX_DESCRIPTION_LENGTH
+            );
+            assert!(
+                listing_metadata
+                    .image_url
+                    .as_ref()
+                    .map_or(true, |image_url| image_url.len() <= MAX_IMAGE_URL_LENGTH),
+                "[Instantiation]: The image URL can not be longer than {} bytes.",
+                MAX_IMAGE_URL_LENGTH
+            );
+            assert!(
+                settlement_reward >= Decimal::zero(),
+                "[Instantiation]: The settlement reward can not be negative."
+            );
+            assert!(
+                starting_price >= Decimal::zero(),
+                "[Instantiation]: The starting price can not be negative."
+            );
+            assert!(
+                early_settlement_rebate_percent >= Decimal::zero()
+                    && early_settlement_rebate_percent <= dec!(100),
+                "[Instantiation]: The early settlement rebate must be between 0 and 100 percent."
+            );
+            assert!(
+                dispute_window_epochs == 0 || dispute_arbiter.is_some(),
+                "[Instantiation]: A dispute arbiter is required when there is a dispute window."
+            );
+            if let Some(payout_conversion) = &payout_conversion {
+                assert!(
+                    payout_conversion.payout_token != accepted_payment_token
+                        && !matches!(
+                            ResourceManager::from_address(payout_conversion.payout_token)
+                                .resource_type(),
+                            ResourceType::NonFungible { id_type: _ }
+                        ),
+                    "[Instantiation]: The payout token must be a fungible token other than the payment token."
+                );
+                assert!(
+                    payout_conversion.min_payout >= Decimal::zero(),
+                    "[Instantiation]: The minimum payout can not be negative."
+                );
+            }
+            assert!(
+                !multi_fill || (non_fungible_tokens.is_empty() && dispute_window_epochs == 0),
+                "[Instantiation]: A multi-fill auction can neither sell NFTs nor have a dispute window."
+            );
+            assert!(
+                settlement_fee_percent == Decimal::zero() || fee_collector.is_some(),
+                "[Instantiation]: A fee collector is required when there is a settlement fee."
+            );
+            assert!(
+                match bid_increment {
+                    BidIncrement::Absolute(amount) => amount > Decimal::zero(),
+                    BidIncrement::Percentage(percent) => percent > Decimal::zero(),
+                },
+                "[Instantiation]: The bid increment must be positive."
+            );
 
             // At this point we know that the component creation can go through.
 
@@ -132,17 +403,29 @@ mod english_auction {
             // When the owner of the NFT(s) instantiates a new english auction sale component, their tokens are taken away
             // from them and they're given an ownership NFT which is used to authenticate them and as proof of ownership
             // of the NFTs. This ownership badge can be used to either withdraw the funds from the token sale or the
-            // NFTs if the seller is no longer interested in selling their tokens.
-            let ownership_badge = ResourceBuilder::new_fungible(OwnerRole::None)
-                .metadata(metadata!(
-                    init {
-                        "name" => "Ownership Badge".to_owned(), locked;
-                        "description" =>
-                        "An ownership badge used to authenticate the owner of the NFT(s).".to_owned(), locked;
-                        "symbol" => "OWNER".to_owned(), locked;
-                    }
-                ))
-                .mint_initial_supply(1);
+            // NFTs if the seller is no longer interested in selling their tokens. Sellers who recorded their account
+            // are authenticated as the owner of that account instead, and get no ownership badge.
+            let ownership_badge: Option<FungibleBucket> = match seller_account {
+                Some(_) => None,
+                None => Some(
+                    ResourceBuilder::new_fungible(OwnerRole::None)
+                        .metadata(metadata!(
+                            init {
+                                "name" => "Ownership Badge".to_owned(), locked;
+                                "description" =>
+                                "An ownership badge used to authenticate the owner of the NFT(s).".to_owned(), locked;
+                                "symbol" => "OWNER".to_owned(), locked;
+                            }
+                        ))
+                        .mint_initial_supply(1),
+                ),
+            };
+            let owner_rule: AccessRule = match seller_account {
+                Some(seller_account) => account_owner_rule(seller_account),
+                None => rule!(require(
+                    ownership_badge.as_ref().unwrap().resource_address()
+                )),
+            };
 
             // Creating the internal admin badge which will be used to manager the bidder badges
             let (address_reservation, component_address) =
@@ -179,20 +462,65 @@ mod english_auction {
             //     .method("withdraw_payment", access_rule.clone(), AccessRule::DenyAll)
             //     .default(rule!(allow_all), AccessRule::DenyAll);
 
+            // Disputes may only be resolved by the holder of the dispute arbiter badge, if there is one.
+            let dispute_arbiter_rule: AccessRule = match dispute_arbiter {
+                Some(dispute_arbiter) => rule!(require(dispute_arbiter)),
+                None => rule!(deny_all),
+            };
+
+            // Settlement fees may only be withdrawn by the holder of the fee collector badge, who is never the seller.
+            let fee_collector_rule: AccessRule = match fee_collector {
+                Some(fee_collector) => rule!(require(fee_collector)),
+                None => rule!(deny_all),
+            };
+
             // Instantiating the english auction sale component
             let english_auction = Self {
                 nft_vaults,
                 bid_vaults: HashMap::new(),
+                proxy_bids: HashMap::new(),
+                multi_fill,
+                lot_vault: None,
+                tranche_bids: HashMap::new(),
+                tranche_bid_count: 0,
+                fills: HashMap::new(),
+                unclaimed_fills: Decimal::zero(),
+                refund_accounts: HashMap::new(),
+                push_refunds,
+                bid_history: Vec::new(),
                 payment_vault: Vault::new(accepted_payment_token),
+                fee_vault: Vault::new(accepted_payment_token),
+                settlement_fee_percent,
+                settlement_reward,
+                settler_account: None,
+                settlement_grace_epochs,
+                early_settlement_rebate_percent,
+                payout_conversion,
+                beneficiary: None,
+                listing_metadata,
+                winning_bid: None,
+                outcome: AuctionOutcome::Pending,
+                dispute_window_epochs,
+                payout_release_epoch: None,
+                disputed: false,
                 bidders_badge: bidder_badge_resource_address,
                 accepted_payment_token,
                 ending_epoch: Runtime::current_epoch().after(relative_ending_epoch).unwrap(),
+                max_extensions,
+                extension_count: 0,
+                starting_price,
+                bid_increment,
+                min_seller_proceeds,
+                reserve_commitment,
+                revealed_reserve: None,
                 state: AuctionState::Open,
             }
                 .instantiate()
-                .prepare_to_globalize(OwnerRole::Updatable(rule!(require(
-                ownership_badge.resource_address()
-            ))))
+                .prepare_to_globalize(OwnerRole::Updatable(owner_rule))
+                .roles(roles!(
+                    dispute_arbiter => dispute_arbiter_rule;
+                    fee_collector => fee_collector_rule;
+                ))
                 .with_address(address_reservation)
                 .globalize();
 
@@ -227,6 +555,9 @@ mod english_auction {
             // At this point we know that the auction can be canceled. So, we withdraw the NFTs and return them to the
             // caller
             self.state = AuctionState::Canceled;
+            if matches!(self.outcome, AuctionOutcome::Pending) {
+                self.outcome = AuctionOutcome::CanceledBySeller;
+            }
 
             let resource_addresses: Vec<ResourceAddress> =
                 self.nft_vaults.keys().cloned().collect();
@@ -240,14 +571,27 @@ mod english_auction {
                 )
             }
 
-            return tokens;
+            // If a beneficiary has been set, the NFTs are sent to them instead of being returned to the caller.
+            return tokens
+                .into_iter()
+                .filter_map(|token| self.deposit_to_beneficiary(token.into()))
+                .map(|token| token.as_non_fungible())
+                .collect();
         }
 
         /// Withdraws the payment owed from the sale.
         ///
-        /// This method performs a single check before canceling the sale:
+        /// This method performs a number of checks before withdrawing the payment:
         ///
         /// * **Check 1:** Checks that the auction is settled.
+        /// * **Check 2:** Checks that the sale is not disputed.
+        /// * **Check 3:** Checks that the dispute window has passed.
+        /// * **Check 4:** Checks that the payout conversion, if any, pays out at least the minimum payout.
+        ///
+        /// A settlement reward which is held in escrow is paid out to the settler first, or kept in the payment if the
+        /// settler's account rejects it. If a payout conversion is set, the payment is then swapped into the payout
+        /// token. Should the swap pay out less than the minimum payout, the withdrawal fails and the payment stays in
+        /// escrow.
         ///
         /// # Returns:
         ///
@@ -269,229 +613,813 @@ mod english_auction {
                 matches!(self.state, AuctionState::Settled),
                 "[Withdraw Payment]: The payment can only be withdrawn when the auction is settled"
             );
+            assert!(
+                !self.disputed,
+                "[Withdraw Payment]: The payment is held until the dispute is resolved."
+            );
+            assert!(
+                Runtime::current_epoch() >= self.payout_release_epoch.unwrap(),
+                "[Withdraw Payment]: The payment is held until the dispute window has passed."
+            );
 
-            // At this point we know that the payment can be withdrawn
-            return self.payment_vault.take_all();
-        }
+            // At this point we know that the payment can be withdrawn.
+            if let Some(settler_account) = self.settler_account.take() {
+                let reward: Bucket = self.payment_vault.take(self.settlement_reward);
+                let mut settler_account: Global<Account> = Global::from(settler_account);
+                if let Some(rejected_reward) = settler_account.try_deposit_or_refund(reward, None) {
+                    self.payment_vault.put(rejected_reward);
+                }
+            }
 
-        // =============================================================================================================
-        // The following are methods which only bidders need and can call.
-        // =============================================================================================================
+            // If a beneficiary has been set, the payment is sent to them and an empty bucket is returned to the caller.
+            let payment: Bucket = self.convert_payout(self.payment_vault.take_all());
+            let payout_token: ResourceAddress = payment.resource_address();
+            return self
+                .deposit_to_beneficiary(payment)
+                .unwrap_or_else(|| Bucket::new(payout_token));
+        }
 
-        /// Allows the caller to Bid in this auction.
+        /// Sets the account which receives the seller's proceeds and any unsold NFTs instead of the holder of the
+        /// ownership badge.
         ///
-        /// This method allows the caller to bid in this auction with their funds. This method will lock up the bidding
-        /// funds and provide the caller with a Bidder's badge in return. The locked up funds can be unlocked at any
-        /// point before the auction ends without any issues. However, after the auction ends, the locked up funds may
-        /// only be unlocked if the bidder did not win the bid.
+        /// Anything the beneficiary's account rejects is returned to the caller instead.
         ///
-        /// This method performs a number of checks before the bid can be made:
+        /// This method performs a number of checks before setting the beneficiary:
         ///
-        /// * **Check 1:** Checks that the auction is in the `Open` state.
+        /// * **Check 1:** Checks that the auction is still `Open`.
+        /// * **Check 2:** Checks that the beneficiary is an account.
         ///
         /// # Arguments:
         ///
-        /// * `funds` (Bucket) - A bucket of the funds to bid.
+        /// * `beneficiary` (ComponentAddress) - The address of the account which should receive the proceeds.
         ///
-        /// # Returns:
+        /// # Note:
         ///
-        /// * `Bucket` - A bucket of the bidder's badge.
-        pub fn bid(&mut self, funds: Bucket) -> Bucket {
+        /// * This is an authenticated method which may only be called by the holder of the `ownership_badge`.
+        pub fn set_beneficiary(&mut self, beneficiary: ComponentAddress) {
             // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
             // met, that the auction will proceed to the next stage/state.
             self.ensure_auction_settlement();
 
-            // Performing checks to ensure that the bid can be added
             assert!(
                 matches!(self.state, AuctionState::Open),
-                "[Bid]: Bids may only be added while the auction is open."
+                "[Set Beneficiary]: The beneficiary can only be changed before the auction is settled."
+            );
+            assert!(
+                beneficiary
+                    .as_node_id()
+                    .entity_type()
+                    .map_or(false, |entity_type| entity_type.is_global_account()),
+                "[Set Beneficiary]: The beneficiary must be an account."
             );
 
-            // At this point we know that a bid can be added.
-
-            // Issuing a bidder's NFT to this bidder with information on the amount that they're bidding
-
-            let bidders_badge: Bucket = self.bidders_badge.mint_ruid_non_fungible(BidderBadge {
-                bid_amount: funds.amount(),
-                is_winner: false,
-            });
-
-            let non_fungible_local_id: NonFungibleLocalId =
-                bidders_badge.as_non_fungible().non_fungible_local_id();
-
-            // Taking the bidder's funds and depositing them into a newly created vault where their funds will now live
-            self.bid_vaults
-                .insert(non_fungible_local_id, Vault::with_bucket(funds));
-
-            // Returning the bidder's badge back to the caller
-            return bidders_badge;
+            self.beneficiary = Some(beneficiary);
         }
 
-        /// Allows a bidder to increase their Bid.
+        /// Reveals the seller's hidden reserve. If it is not revealed before the auction settles, the highest bid wins
+        /// regardless of the reserve.
         ///
-        /// This is an authenticated which which allows bidders to increase the amount that they are bidding in the
-        /// auction to increase their chances of winning the bid.
-        ///
-        /// This method performs a number of checks before the bid can be increased:
+        /// This method performs a number of checks before the reserve is revealed:
         ///
-        /// * **Check 1:** Checks that the auction is in the `Open` state.
-        /// * **Check 2:** Checks that the payment was provided in the required token.
-        /// * **Check 3:** Checks that the badge provided is a valid bidder's badge.
-        /// * **Check 4:** Checks that the `Proof` contains a single bidder's badge.
+        /// * **Check 1:** Checks that the auction is still `Open`.
+        /// * **Check 2:** Checks that the seller committed to a reserve which has not been revealed yet.
+        /// * **Check 3:** Checks that the reserve and nonce match the commitment.
         ///
         /// # Arguments:
         ///
-        /// * `funds` (Bucket) - A bucket of the funds the bidder wishes to add to their bid.
-        /// * `bidders_badge` (Proof) - A `Proof` of the bidder's badge.
-        pub fn increase_bid(&mut self, funds: Bucket, bidders_badge: Proof) {
+        /// * `reserve` (Decimal) - The hidden reserve.
+        /// * `nonce` (String) - The nonce which was hashed together with the reserve.
+        ///
+        /// # Note:
+        ///
+        /// * This is an authenticated method which may only be called by the holder of the `ownership_badge`.
+        pub fn reveal_reserve(&mut self, reserve: Decimal, nonce: String) {
             // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
             // met, that the auction will proceed to the next stage/state.
             self.ensure_auction_settlement();
 
-            // Checking if the bid can be increased or not.
             assert!(
                 matches!(self.state, AuctionState::Open),
-                "[Increase Bid]: Bids may only be increased while the auction is open."
-            );
-            assert_eq!(
-                funds.resource_address(),
-                self.accepted_payment_token,
-                "[Increase Bid]: Invalid tokens were provided as bid. Bids are only allowed in {:?}",
-                self.accepted_payment_token
+                "[Reveal Reserve]: The reserve can only be revealed before the auction is settled."
             );
-            let bidders_badge = bidders_badge.check(self.bidders_badge.address());
-
-            assert_eq!(
-                bidders_badge.amount(), Decimal::one(),
-                "[Increase Bid]: This method requires that exactly one bidder's badge is passed to the method"
+            let reserve_commitment: [u8; 32] = self
+                .reserve_commitment
+                .expect("[Reveal Reserve]: The seller did not commit to a reserve.");
+            assert!(
+                self.revealed_reserve.is_none(),
+                "[Reveal Reserve]: The reserve has already been revealed."
             );
 
-            // At this point we know that the bidder's bid can be increased.
-
-            // Updating the metadata of the bidder's badge to reflect on the update of the bidder's bid
-            let bidders_badge_data: BidderBadge =
-                bidders_badge.as_non_fungible().non_fungible().data();
-            let non_fungible_local_id: NonFungibleLocalId =
-                bidders_badge.as_non_fungible().non_fungible_local_id();
-            let resource_manager = self.bidders_badge;
-            resource_manager.update_non_fungible_data(
-                &non_fungible_local_id,
-                "bid_amount",
-                bidders_badge_data.bid_amount.checked_add(funds.amount()),
+            let hash: [u8; 32] = Sha256::digest(scrypto_encode(&(reserve, nonce)).unwrap()).into();
+            assert!(
+                hash == reserve_commitment,
+                "[Reveal Reserve]: The reserve and nonce do not match the commitment."
             );
 
-            // Adding the funds to the vault of the bidder
-            self.bid_vaults
-                .get_mut(
-                    &bidders_badge
-                        .as_non_fungible()
-                        .non_fungible::<BidderBadge>()
-                        .local_id(),
-                )
-                .unwrap()
-                .put(funds);
+            self.revealed_reserve = Some(reserve);
         }
 
-        /// Allows bidders to cancel their bids.
-        ///
-        /// This method allows bidders to cancel their bids, burning their badge in the process and taking out the funds
-        /// which were locked up in the component for the bid.
-        ///
-        /// A bid may be canceled in one of the following situations:
-        ///
-        /// 1. Before the auction has ended if the bidder is no longer interested in bidding in this auction.
-        /// 2. After the end of the auction if the bidder is not the winner of the auction.
+        /// Deposits the fungible lot sold in a multi-fill auction. The lot may be deposited in several parts, as long
+        /// as nobody has bid yet.
         ///
-        /// This method performs a number of checks before the bid is cancelled
+        /// This method performs a number of checks before the lot is deposited:
         ///
-        /// * **Check 1:** Checks that the badge provided is a valid bidder's badge.
-        /// * **Check 2:** Checks that the `Proof` contains a single bidder's badge.
-        /// * **Check 3:** Checks that the badge provided is not the winner's badge.
+        /// * **Check 1:** Checks that the auction is a multi-fill auction.
+        /// * **Check 2:** Checks that the auction is `Open` and has no bids.
+        /// * **Check 3:** Checks that the lot is a fungible token, the same as any part deposited before.
         ///
         /// # Arguments:
         ///
-        /// * `bidders_badge` (Bucket) - A `Bucket` of the bidder's badge.
+        /// * `lot` (Bucket) - A bucket of the fungible tokens to sell.
         ///
-        /// # Returns:
+        /// # Note:
         ///
-        /// * `Bucket` - A bucket of the funds owed to the bidder.
-        pub fn cancel_bid(&mut self, bidders_badge: Bucket) -> Bucket {
+        /// * This is an authenticated method which may only be called by the holder of the `ownership_badge`.
+        pub fn deposit_lot(&mut self, lot: Bucket) {
             // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
             // met, that the auction will proceed to the next stage/state.
             self.ensure_auction_settlement();
 
-            // Checking if the bid can be canceled or not.
-            // assert_eq!(
-            //     bidders_badge.resource_address(),
-            //     self.bidders_badge,
-            //     "[Cancel Bid]: Badge provided is not a valid bidder's badge"
-            // );
-            assert_eq!(
-                bidders_badge.amount(), Decimal::one(),
-                "[Cancel Bid]: This method requires that exactly one bidder's badge is passed to the method"
+            assert!(
+                self.multi_fill,
+                "[Deposit Lot]: Only a multi-fill auction sells a fungible lot."
             );
             assert!(
-                !bidders_badge
-                    .as_non_fungible()
-                    .non_fungible::<BidderBadge>()
-                    .data()
-                    .is_winner,
-                "[Cancel Bid]: You can not cancel your bid after winning the auction."
+                matches!(self.state, AuctionState::Open) && !self.has_bids(),
+                "[Deposit Lot]: The lot can only be deposited while the auction is open and has no bids."
             );
-            // At this point we know that the bid cancellation can go on.
-            // Take out the bidder's funds from their vault
-            let funds: Bucket = self
-                .bid_vaults
-                .get_mut(
-                    &bidders_badge
-                        .as_non_fungible()
-                        .non_fungible::<BidderBadge>()
-                        .local_id(),
-                )
-                .unwrap()
-                .take_all();
-            // This bidder will no longer need their badge. We can now safely burn the badge.
-            bidders_badge.burn();
-            // The bidder's funds may now be returned to them
-            return funds;
+            assert!(
+                !matches!(
+                    ResourceManager::from_address(lot.resource_address()).resource_type(),
+                    ResourceType::NonFungible { id_type: _ }
+                ),
+                "[Deposit Lot]: The lot must be a fungible token."
+            );
+
+            match &mut self.lot_vault {
+                Some(lot_vault) => {
+                    assert_eq!(
+                        lot.resource_address(),
+                        lot_vault.resource_address(),
+                        "[Deposit Lot]: The lot must be a single token."
+                    );
+                    lot_vault.put(lot);
+                }
+                None => self.lot_vault = Some(Vault::with_bucket(lot)),
+            }
         }
 
-        /// Allows the winning bidder to claim their NFTs.
+        /// Withdraws the part of the lot of a multi-fill auction which was not sold, or the whole lot if the auction
+        /// was canceled.
         ///
-        /// This is a method which allows the winning bidder to claim their NFTs from the component. This method
-        /// requires that the bidder passes a bucket with their winning bidder's badge which is burned and in exchange
-        /// for that, the caller is given the NFTs which had been locked up in this component.
+        /// This method performs a single check before withdrawing the lot:
         ///
-        /// This method performs a number of checks before unlocking the NFTs:
+        /// * **Check 1:** Checks that the auction is either `Settled` or `Canceled`.
         ///
-        /// * **Check 1:** Checks that the auction state is `Settled`.
-        /// * **Check 2:** Checks that the badge provided is a valid bidder's badge.
-        /// * **Check 3:** Checks that the `Proof` contains a single bidder's badge.
-        /// * **Check 4:** Checks that the badge provided is the winner's badge.
+        /// # Returns:
         ///
-        /// # Arguments:
+        /// * `Bucket` - A bucket of the unsold lot.
         ///
-        /// * `bidders_badge` (Bucket) - A `Bucket` of the bidder's badge.
+        /// # Note:
+        ///
+        /// * This is an authenticated method which may only be called by the holder of the `ownership_badge`.
+        pub fn withdraw_unsold_lot(&mut self) -> Bucket {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            assert!(
+                matches!(self.state, AuctionState::Settled)
+                    || matches!(self.state, AuctionState::Canceled),
+                "[Withdraw Unsold Lot]: The lot can only be withdrawn once the auction has ended."
+            );
+
+            let unclaimed_fills: Decimal = self.unclaimed_fills;
+            let lot_vault: &mut Vault = self
+                .lot_vault
+                .as_mut()
+                .expect("[Withdraw Unsold Lot]: No lot has been deposited.");
+            let unsold: Decimal = lot_vault.amount().checked_sub(unclaimed_fills).unwrap();
+            return lot_vault.take(unsold);
+        }
+
+        /// Withdraws the settlement fees which were kept from the winning bid.
+        ///
+        /// This method performs a single check before withdrawing the fees:
+        ///
+        /// * **Check 1:** Checks that the sale is final: either the auction was canceled or it is settled, undisputed
+        /// and its dispute window has passed.
         ///
         /// # Returns:
         ///
-        /// * `Vec<Bucket>` - A vector of buckets of the non-fungible tokens which were being auctioned.
-        pub fn claim_nfts(&mut self, bidders_badge: Bucket) -> Vec<NonFungibleBucket> {
+        /// * `Bucket` - A bucket containing the settlement fees. This bucket is empty if the auction did not sell.
+        ///
+        /// # Note:
+        ///
+        /// * This is an authenticated method which may only be called by the holder of the fee collector badge.
+        pub fn withdraw_fees(&mut self) -> Bucket {
             // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
             // met, that the auction will proceed to the next stage/state.
             self.ensure_auction_settlement();
 
-            // Checking if the NFTs in this component can be claimed
             assert!(
-                matches!(self.state, AuctionState::Settled),
-                "[Claim NFTs]: NFTs can only be claimed when the auction has settled."
+                match self.state {
+                    AuctionState::Open => false,
+                    AuctionState::Settled => {
+                        !self.disputed
+                            && Runtime::current_epoch() >= self.payout_release_epoch.unwrap()
+                    }
+                    AuctionState::Canceled => true,
+                },
+                "[Withdraw Fees]: The fees can only be withdrawn once the sale is final."
             );
 
-            // assert_eq!(
-            //     bidders_badge.resource_address(),
-            //     self.bidders_badge,
-            //     "[Claim NFTs]: Badge provided is not a valid bidder's badge"
-            // );
+            return self.fee_vault.take_all();
+        }
+
+        /// Withdraws the seller's own assets which are still held in escrow long after the auction has ended.
+        ///
+        /// This is a safety valve for assets which would otherwise be stranded, for example if the auction can not be
+        /// settled. Unlike the other methods, it does not attempt to settle the auction first. An auction which never
+        /// settled is canceled, and the seller gets back the NFTs and the lot. Of a settled auction, the seller gets
+        /// the undisputed proceeds and the part of the lot which was not sold. The NFTs and fills won by bidders, the
+        /// bids, the settlement fees and any settlement reward held in escrow are left in the auction, where their
+        /// owners may still withdraw them.
+        ///
+        /// This method performs a single check before withdrawing:
+        ///
+        /// * **Check 1:** Checks that `RESCUE_DELAY_EPOCHS` epochs have passed since the ending epoch.
+        ///
+        /// # Returns:
+        ///
+        /// * `Vec<Bucket>` - A vector of buckets of the non-fungible tokens which the seller gets back.
+        /// * `Bucket` - A bucket of the seller's proceeds.
+        /// * `Option<Bucket>` - A bucket of the unsold part of the lot of a multi-fill auction, if any.
+        ///
+        /// If a beneficiary has been set, all of the above are sent to them instead, and only what their account rejects
+        /// is returned.
+        ///
+        /// # Note:
+        ///
+        /// * This is an authenticated method which may only be called by the holder of the `ownership_badge`.
+        pub fn emergency_withdraw(&mut self) -> (Vec<NonFungibleBucket>, Bucket, Option<Bucket>) {
+            assert!(
+                Runtime::current_epoch().number()
+                    > self
+                        .ending_epoch
+                        .number()
+                        .saturating_add(RESCUE_DELAY_EPOCHS),
+                "[Emergency Withdraw]: Can only be called {} epochs after the end of the auction.",
+                RESCUE_DELAY_EPOCHS
+            );
+
+            // An auction which never settled is canceled so that its bidders may withdraw their bids.
+            if matches!(self.state, AuctionState::Open) {
+                self.state = AuctionState::Canceled;
+                self.outcome = AuctionOutcome::CanceledBySeller;
+            }
+            let settled: bool = matches!(self.state, AuctionState::Settled);
+
+            // The NFTs belong to the winner once the auction has settled.
+            let mut tokens: Vec<NonFungibleBucket> = Vec::new();
+            if !settled {
+                for nft_vault in self.nft_vaults.values_mut() {
+                    tokens.push(nft_vault.take_all());
+                }
+            }
+
+            // The proceeds belong to the seller unless they are disputed, except for a settlement reward held in
+            // escrow which is still owed to the settler.
+            let mut payment: Bucket = Bucket::new(self.accepted_payment_token);
+            if settled && !self.disputed {
+                let escrowed_reward: Decimal = match self.settler_account {
+                    Some(_) => self.settlement_reward,
+                    None => Decimal::zero(),
+                };
+                let proceeds: Decimal = self
+                    .payment_vault
+                    .amount()
+                    .checked_sub(escrowed_reward)
+                    .unwrap();
+                payment.put(self.payment_vault.take(proceeds));
+            }
+
+            // The fills allocated to the winners are kept for them to claim.
+            let unclaimed_fills: Decimal = self.unclaimed_fills;
+            let lot: Option<Bucket> = self.lot_vault.as_mut().map(|lot_vault| {
+                let unsold: Decimal = lot_vault.amount().checked_sub(unclaimed_fills).unwrap();
+                lot_vault.take(unsold)
+            });
+
+            let tokens: Vec<NonFungibleBucket> = tokens
+                .into_iter()
+                .filter_map(|token| self.deposit_to_beneficiary(token.into()))
+                .map(|token| token.as_non_fungible())
+                .collect();
+            let payment: Bucket = self
+                .deposit_to_beneficiary(payment)
+                .unwrap_or_else(|| Bucket::new(self.accepted_payment_token));
+            let lot: Option<Bucket> = lot.and_then(|lot| self.deposit_to_beneficiary(lot));
+
+            return (tokens, payment, lot);
+        }
+
+        // =============================================================================================================
+        // The following are methods which only bidders need and can call.
+        // =============================================================================================================
+
+        /// Allows the caller to Bid in this auction.
+        ///
+        /// This method allows the caller to bid in this auction with their funds. This method will lock up the bidding
+        /// funds and provide the caller with a Bidder's badge in return. The locked up funds can be unlocked at any
+        /// point before the auction ends without any issues. However, after the auction ends, the locked up funds may
+        /// only be unlocked if the bidder did not win the bid.
+        ///
+        /// This method performs a number of checks before the bid can be made:
+        ///
+        /// * **Check 1:** Checks that the auction is in the `Open` state.
+        /// * **Check 2:** Checks that the payment was provided in the required token.
+        /// * **Check 3:** Checks that the bid is not empty.
+        /// * **Check 4:** Checks that the bid is at least the starting price and the minimum next bid.
+        ///
+        /// # Arguments:
+        ///
+        /// * `funds` (Bucket) - A bucket of the funds to bid.
+        ///
+        /// # Returns:
+        ///
+        /// * `Bucket` - A bucket of the bidder's badge.
+        pub fn bid(&mut self, funds: Bucket) -> Bucket {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            // Performing checks to ensure that the bid can be added
+            assert!(
+                matches!(self.state, AuctionState::Open),
+                "[Bid]: Bids may only be added while the auction is open."
+            );
+            assert!(
+                !self.multi_fill,
+                "[Bid]: Bids in a multi-fill auction must be placed through `bid_for_quantity`."
+            );
+            assert_eq!(
+                funds.resource_address(),
+                self.accepted_payment_token,
+                "[Bid]: Invalid tokens were provided as bid. Bids are only allowed in {:?}",
+                self.accepted_payment_token
+            );
+            assert!(!funds.is_empty(), "[Bid]: Can not accept an empty bid.");
+            assert!(
+                funds.amount() >= self.starting_price,
+                "[Bid]: The bid is below the starting price of {}.",
+                self.starting_price
+            );
+            let min_next_bid: Decimal = self.min_next_bid();
+            assert!(
+                funds.amount() >= min_next_bid,
+                "[Bid]: The bid is below the minimum next bid of {}.",
+                min_next_bid
+            );
+
+            // At this point we know that a bid can be added.
+
+            // Issuing a bidder's NFT to this bidder with information on the amount that they're bidding
+
+            let bidders_badge: Bucket = self.bidders_badge.mint_ruid_non_fungible(BidderBadge {
+                bid_amount: funds.amount(),
+                is_winner: false,
+            });
+
+            let non_fungible_local_id: NonFungibleLocalId =
+                bidders_badge.as_non_fungible().non_fungible_local_id();
+
+            // Taking the bidder's funds and depositing them into a newly created vault where their funds will now live
+            self.record_bid(non_fungible_local_id.clone(), funds.amount());
+            self.bid_vaults
+                .insert(non_fungible_local_id, Vault::with_bucket(funds));
+
+            // Proxy bidders may need to be advanced to stay ahead of the new bid.
+            self.update_proxy_bids();
+            self.push_outbid_refunds();
+            self.extend_if_late_bid();
+
+            // Returning the bidder's badge back to the caller
+            return bidders_badge;
+        }
+
+        /// Allows the caller to Bid in this auction and to be refunded to their account when they are outbid.
+        ///
+        /// This method is the same as `bid`, except that if the auction pushes refunds, the bidder's funds are
+        /// deposited into `refund_account` as soon as another bid outbids theirs. If the deposit is rejected, the funds
+        /// stay in the auction and may be withdrawn through `cancel_bid` as usual. Proxy bids are never pushed, as
+        /// they may still be advanced by the auction.
+        ///
+        /// This method performs the same checks as `bid`, and additionally checks that the refund account is an
+        /// account, since pushing a refund to any other component would fail every bid which outbids this one.
+        ///
+        /// # Arguments:
+        ///
+        /// * `funds` (Bucket) - A bucket of the funds to bid.
+        /// * `refund_account` (ComponentAddress) - The account to push the funds to when the bid is outbid.
+        ///
+        /// # Returns:
+        ///
+        /// * `Bucket` - A bucket of the bidder's badge.
+        pub fn bid_with_refund_account(
+            &mut self,
+            funds: Bucket,
+            refund_account: ComponentAddress,
+        ) -> Bucket {
+            assert!(
+                refund_account
+                    .as_node_id()
+                    .entity_type()
+                    .map_or(false, |entity_type| entity_type.is_global_account()),
+                "[Bid]: The refund account must be an account."
+            );
+
+            let bidders_badge: Bucket = self.bid(funds);
+            self.refund_accounts.insert(
+                bidders_badge.as_non_fungible().non_fungible_local_id(),
+                refund_account,
+            );
+
+            return bidders_badge;
+        }
+
+        /// Allows the caller to place a proxy bid in this auction.
+        ///
+        /// A proxy bid escrows the bidder's maximum bid, but the auction only bids as much as is needed to stay ahead
+        /// of the highest competing bid (by the bid increment), up to the maximum. Whenever another bid comes in,
+        /// the proxy bid is automatically advanced. When the auction settles, only the effective bid is paid and the
+        /// unused escrow is returned to the winner when they claim their NFTs.
+        ///
+        /// This method performs a number of checks before the proxy bid can be made:
+        ///
+        /// * **Check 1:** Checks that the auction is in the `Open` state.
+        /// * **Check 2:** Checks that the payment was provided in the required token.
+        /// * **Check 3:** Checks that the maximum bid is positive, representable in the payment token and covered by the
+        /// payment.
+        ///
+        /// # Arguments:
+        ///
+        /// * `max` (Decimal) - The maximum amount that the auction may bid on behalf of the bidder.
+        /// * `payment` (Bucket) - A bucket of funds covering the maximum bid.
+        ///
+        /// # Returns:
+        ///
+        /// * `Bucket` - A bucket of the bidder's badge.
+        /// * `Bucket` - A bucket of the payment in excess of the maximum bid.
+        pub fn place_proxy_bid(&mut self, max: Decimal, mut payment: Bucket) -> (Bucket, Bucket) {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            // Performing checks to ensure that the proxy bid can be added
+            assert!(
+                matches!(self.state, AuctionState::Open),
+                "[Proxy Bid]: Bids may only be added while the auction is open."
+            );
+            assert!(
+                !self.multi_fill,
+                "[Proxy Bid]: Bids in a multi-fill auction must be placed through `bid_for_quantity`."
+            );
+            assert_eq!(
+                payment.resource_address(),
+                self.accepted_payment_token,
+                "[Proxy Bid]: Invalid tokens were provided as bid. Bids are only allowed in {:?}",
+                self.accepted_payment_token
+            );
+            assert!(
+                max > Decimal::zero(),
+                "[Proxy Bid]: The maximum bid must be positive."
+            );
+            assert!(
+                max >= self.starting_price,
+                "[Proxy Bid]: The maximum bid is below the starting price of {}.",
+                self.starting_price
+            );
+            self.assert_bid_precision(max);
+            assert!(
+                payment.amount() >= max,
+                "[Proxy Bid]: The payment does not cover the maximum bid of {}.",
+                max
+            );
+
+            // At this point we know that the proxy bid can be added.
+            let bidders_badge: Bucket = self.bidders_badge.mint_ruid_non_fungible(BidderBadge {
+                bid_amount: max,
+                is_winner: false,
+            });
+            let non_fungible_local_id: NonFungibleLocalId =
+                bidders_badge.as_non_fungible().non_fungible_local_id();
+
+            self.bid_vaults.insert(
+                non_fungible_local_id.clone(),
+                Vault::with_bucket(payment.take(max)),
+            );
+            self.proxy_bids.insert(
+                non_fungible_local_id.clone(),
+                ProxyBid {
+                    max_amount: max,
+                    current_amount: Decimal::zero(),
+                },
+            );
+            self.update_proxy_bids();
+
+            // Only the amount bid on behalf of the bidder is recorded, as the maximum stays hidden.
+            self.record_bid(
+                non_fungible_local_id.clone(),
+                self.effective_bid(&non_fungible_local_id),
+            );
+            self.push_outbid_refunds();
+            self.extend_if_late_bid();
+
+            return (bidders_badge, payment);
+        }
+
+        /// Allows the caller to bid for a quantity of the lot in a multi-fill auction.
+        ///
+        /// The price per unit of the bid is the amount of funds divided by the quantity. When the auction settles, the
+        /// bids with the highest price per unit are filled first. The marginal bid may only be partially filled, in
+        /// which case it only pays for its fill and the rest of its funds are returned when the lot is claimed.
+        ///
+        /// This method performs a number of checks before the bid can be made:
+        ///
+        /// * **Check 1:** Checks that the auction is a multi-fill auction in the `Open` state.
+        /// * **Check 2:** Checks that the lot has been deposited.
+        /// * **Check 3:** Checks that the payment was provided in the required token.
+        /// * **Check 4:** Checks that the quantity is positive, no more than the lot, and representable in the token
+        /// of the lot.
+        /// * **Check 5:** Checks that the price per unit is at least the starting price.
+        ///
+        /// # Arguments:
+        ///
+        /// * `funds` (Bucket) - A bucket of the funds to bid.
+        /// * `quantity` (Decimal) - The quantity of the lot to bid for.
+        ///
+        /// # Returns:
+        ///
+        /// * `Bucket` - A bucket of the bidder's badge.
+        pub fn bid_for_quantity(&mut self, funds: Bucket, quantity: Decimal) -> Bucket {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            // Performing checks to ensure that the bid can be added
+            assert!(
+                self.multi_fill && matches!(self.state, AuctionState::Open),
+                "[Tranche Bid]: Bids for a quantity may only be added while a multi-fill auction is open."
+            );
+            let lot_vault: &Vault = self
+                .lot_vault
+                .as_ref()
+                .expect("[Tranche Bid]: The lot has not been deposited yet.");
+            assert_eq!(
+                funds.resource_address(),
+                self.accepted_payment_token,
+                "[Tranche Bid]: Invalid tokens were provided as bid. Bids are only allowed in {:?}",
+                self.accepted_payment_token
+            );
+            assert!(
+                quantity > Decimal::zero() && quantity <= lot_vault.amount(),
+                "[Tranche Bid]: The quantity must be positive and no more than the lot of {}.",
+                lot_vault.amount()
+            );
+            let lot_divisibility: u8 = Self::divisibility(lot_vault.resource_address());
+            assert!(
+                quantity.checked_round(lot_divisibility, RoundingMode::ToZero) == Some(quantity),
+                "[Tranche Bid]: The quantity exceeds the divisibility of the lot."
+            );
+            assert!(
+                funds.amount() >= quantity.checked_mul(self.starting_price).unwrap(),
+                "[Tranche Bid]: The price per unit is below the starting price of {}.",
+                self.starting_price
+            );
+
+            // At this point we know that the bid can be added.
+            let bidders_badge: Bucket = self.bidders_badge.mint_ruid_non_fungible(BidderBadge {
+                bid_amount: funds.amount(),
+                is_winner: false,
+            });
+            let non_fungible_local_id: NonFungibleLocalId =
+                bidders_badge.as_non_fungible().non_fungible_local_id();
+
+            self.record_bid(non_fungible_local_id.clone(), funds.amount());
+            self.tranche_bids.insert(
+                non_fungible_local_id.clone(),
+                TrancheBid {
+                    quantity,
+                    sequence: self.tranche_bid_count,
+                },
+            );
+            self.tranche_bid_count += 1;
+            self.bid_vaults
+                .insert(non_fungible_local_id, Vault::with_bucket(funds));
+            self.extend_if_late_bid();
+
+            return bidders_badge;
+        }
+
+        /// Allows a bidder to increase their Bid.
+        ///
+        /// This is an authenticated which which allows bidders to increase the amount that they are bidding in the
+        /// auction to increase their chances of winning the bid.
+        ///
+        /// This method performs a number of checks before the bid can be increased:
+        ///
+        /// * **Check 1:** Checks that the auction is in the `Open` state.
+        /// * **Check 2:** Checks that the payment was provided in the required token.
+        /// * **Check 3:** Checks that the funds provided are not empty.
+        /// * **Check 4:** Checks that the badge provided is a valid bidder's badge.
+        /// * **Check 5:** Checks that the `Proof` contains a single bidder's badge.
+        ///
+        /// # Arguments:
+        ///
+        /// * `funds` (Bucket) - A bucket of the funds the bidder wishes to add to their bid.
+        /// * `bidders_badge` (Proof) - A `Proof` of the bidder's badge.
+        pub fn increase_bid(&mut self, funds: Bucket, bidders_badge: Proof) {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            // Checking if the bid can be increased or not.
+            assert!(
+                matches!(self.state, AuctionState::Open),
+                "[Increase Bid]: Bids may only be increased while the auction is open."
+            );
+            assert_eq!(
+                funds.resource_address(),
+                self.accepted_payment_token,
+                "[Increase Bid]: Invalid tokens were provided as bid. Bids are only allowed in {:?}",
+                self.accepted_payment_token
+            );
+            assert!(
+                !funds.is_empty(),
+                "[Increase Bid]: Can not increase a bid by an empty bid."
+            );
+            let bidders_badge = bidders_badge.check(self.bidders_badge.address());
+
+            assert_eq!(
+                bidders_badge.amount(), Decimal::one(),
+                "[Increase Bid]: This method requires that exactly one bidder's badge is passed to the method"
+            );
+
+            assert!(
+                !self.multi_fill,
+                "[Increase Bid]: Bids in a multi-fill auction can not be increased."
+            );
+
+            // At this point we know that the bidder's bid can be increased.
+
+            // Updating the metadata of the bidder's badge to reflect on the update of the bidder's bid
+            let bidders_badge_data: BidderBadge =
+                bidders_badge.as_non_fungible().non_fungible().data();
+            let non_fungible_local_id: NonFungibleLocalId =
+                bidders_badge.as_non_fungible().non_fungible_local_id();
+            let resource_manager = self.bidders_badge;
+            let new_bid_amount = bidders_badge_data
+                .bid_amount
+                .checked_add(funds.amount())
+                .unwrap();
+            resource_manager.update_non_fungible_data(
+                &non_fungible_local_id,
+                "bid_amount",
+                new_bid_amount,
+            );
+
+            // Adding the funds to the vault of the bidder. For proxy bids, the funds raise the maximum bid.
+            if let Some(proxy_bid) = self.proxy_bids.get_mut(&non_fungible_local_id) {
+                proxy_bid.max_amount = proxy_bid.max_amount.checked_add(funds.amount()).unwrap();
+            }
+            self.bid_vaults
+                .get_mut(&non_fungible_local_id)
+                .unwrap()
+                .put(funds);
+
+            self.update_proxy_bids();
+
+            // For proxy bids, only the amount bid on behalf of the bidder is recorded, as the maximum stays hidden.
+            self.record_bid(
+                non_fungible_local_id.clone(),
+                self.effective_bid(&non_fungible_local_id),
+            );
+            self.push_outbid_refunds();
+            self.extend_if_late_bid();
+        }
+
+        /// Allows bidders to cancel their bids.
+        ///
+        /// This method allows bidders to cancel their bids, burning their badge in the process and taking out the funds
+        /// which were locked up in the component for the bid.
+        ///
+        /// A bid may be canceled in one of the following situations:
+        ///
+        /// 1. Before the auction has ended if the bidder is no longer interested in bidding in this auction.
+        /// 2. After the end of the auction if the bidder is not the winner of the auction.
+        ///
+        /// This method performs a number of checks before the bid is cancelled
+        ///
+        /// * **Check 1:** Checks that the badge provided is a valid bidder's badge.
+        /// * **Check 2:** Checks that the `Proof` contains a single bidder's badge.
+        /// * **Check 3:** Checks that the badge provided is not the winner's badge.
+        ///
+        /// # Arguments:
+        ///
+        /// * `bidders_badge` (Bucket) - A `Bucket` of the bidder's badge.
+        ///
+        /// # Returns:
+        ///
+        /// * `Bucket` - A bucket of the funds owed to the bidder.
+        pub fn cancel_bid(&mut self, bidders_badge: Bucket) -> Bucket {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            // Checking if the bid can be canceled or not.
+            // assert_eq!(
+            //     bidders_badge.resource_address(),
+            //     self.bidders_badge,
+            //     "[Cancel Bid]: Badge provided is not a valid bidder's badge"
+            // );
+            assert_eq!(
+                bidders_badge.amount(), Decimal::one(),
+                "[Cancel Bid]: This method requires that exactly one bidder's badge is passed to the method"
+            );
+            assert!(
+                !bidders_badge
+                    .as_non_fungible()
+                    .non_fungible::<BidderBadge>()
+                    .data()
+                    .is_winner,
+                "[Cancel Bid]: You can not cancel your bid after winning the auction."
+            );
+            // At this point we know that the bid cancellation can go on.
+            // Take out the bidder's funds from their vault
+            let non_fungible_local_id: NonFungibleLocalId =
+                bidders_badge.as_non_fungible().non_fungible_local_id();
+            let funds: Bucket = self
+                .bid_vaults
+                .get_mut(&non_fungible_local_id)
+                .unwrap()
+                .take_all();
+            if self.proxy_bids.remove(&non_fungible_local_id).is_some() {
+                self.update_proxy_bids();
+            }
+            self.tranche_bids.remove(&non_fungible_local_id);
+            // This bidder will no longer need their badge. We can now safely burn the badge.
+            bidders_badge.burn();
+            // The bidder's funds may now be returned to them
+            return funds;
+        }
+
+        /// Allows the winning bidder to claim their NFTs.
+        ///
+        /// This is a method which allows the winning bidder to claim their NFTs from the component. This method
+        /// requires that the bidder passes a bucket with their winning bidder's badge which is burned and in exchange
+        /// for that, the caller is given the NFTs which had been locked up in this component along with any of their
+        /// escrowed funds which were not needed for the winning bid (e.g. the unused part of a proxy bid).
+        ///
+        /// This method performs a number of checks before unlocking the NFTs:
+        ///
+        /// * **Check 1:** Checks that the auction state is `Settled`.
+        /// * **Check 2:** Checks that the badge provided is a valid bidder's badge.
+        /// * **Check 3:** Checks that the `Proof` contains a single bidder's badge.
+        /// * **Check 4:** Checks that the badge provided is the winner's badge.
+        ///
+        /// # Arguments:
+        ///
+        /// * `bidders_badge` (Bucket) - A `Bucket` of the bidder's badge.
+        ///
+        /// # Returns:
+        ///
+        /// * `Vec<Bucket>` - A vector of buckets of the non-fungible tokens which were being auctioned.
+        /// * `Bucket` - A bucket of the winner's unused escrowed funds.
+        pub fn claim_nfts(&mut self, bidders_badge: Bucket) -> (Vec<NonFungibleBucket>, Bucket) {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            // Checking if the NFTs in this component can be claimed
+            assert!(
+                matches!(self.state, AuctionState::Settled),
+                "[Claim NFTs]: NFTs can only be claimed when the auction has settled."
+            );
+            assert!(
+                !self.disputed,
+                "[Claim NFTs]: NFTs can not be claimed while the sale is disputed."
+            );
+            assert!(
+                !self.multi_fill,
+                "[Claim NFTs]: The winners of a multi-fill auction claim their fill through `claim_fill`."
+            );
+
+            // assert_eq!(
+            //     bidders_badge.resource_address(),
+            //     self.bidders_badge,
+            //     "[Claim NFTs]: Badge provided is not a valid bidder's badge"
+            // );
             assert_eq!(
                 bidders_badge.amount(), Decimal::one(),
                 "[Claim NFTs]: This method requires that exactly one bidder's badge is passed to the method"
@@ -507,23 +1435,89 @@ mod english_auction {
 
             // At this point we know that the NFTs can be claimed from the component
 
+            // Any funds left in the winner's vault were not needed for the winning bid and are returned.
+            let unused_funds: Bucket = self
+                .bid_vaults
+                .get_mut(&bidders_badge.as_non_fungible().non_fungible_local_id())
+                .unwrap()
+                .take_all();
+
             // We can safely burn the bidder's badge at this point as it is no longer needed by the bidder.
             bidders_badge.burn();
 
-            // Getting all of the NFTs from the auction and returning them to the caller
-            let resource_addresses: Vec<ResourceAddress> =
-                self.nft_vaults.keys().cloned().collect();
-            let mut tokens: Vec<NonFungibleBucket> = Vec::new();
-            for resource_address in resource_addresses.into_iter() {
-                tokens.push(
-                    self.nft_vaults
-                        .get_mut(&resource_address)
-                        .unwrap()
-                        .take_all(),
-                )
-            }
-
-            return tokens;
+            // Getting all of the NFTs from the auction and returning them to the caller
+            let resource_addresses: Vec<ResourceAddress> =
+                self.nft_vaults.keys().cloned().collect();
+            let mut tokens: Vec<NonFungibleBucket> = Vec::new();
+            for resource_address in resource_addresses.into_iter() {
+                tokens.push(
+                    self.nft_vaults
+                        .get_mut(&resource_address)
+                        .unwrap()
+                        .take_all(),
+                )
+            }
+
+            return (tokens, unused_funds);
+        }
+
+        /// Allows a winning bidder of a multi-fill auction to claim their fill of the lot.
+        ///
+        /// This method performs a number of checks before the fill is claimed:
+        ///
+        /// * **Check 1:** Checks that the auction is a multi-fill auction in the `Settled` state.
+        /// * **Check 2:** Checks that the `Bucket` contains a single bidder's badge.
+        /// * **Check 3:** Checks that the badge provided is a winner's badge.
+        ///
+        /// # Arguments:
+        ///
+        /// * `bidders_badge` (Bucket) - A `Bucket` of the bidder's badge.
+        ///
+        /// # Returns:
+        ///
+        /// * `Bucket` - A bucket of the bidder's fill of the lot.
+        /// * `Bucket` - A bucket of the bidder's funds which were not needed to pay for their fill.
+        pub fn claim_fill(&mut self, bidders_badge: Bucket) -> (Bucket, Bucket) {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            assert!(
+                self.multi_fill && matches!(self.state, AuctionState::Settled),
+                "[Claim Fill]: Fills can only be claimed when a multi-fill auction has settled."
+            );
+            assert_eq!(
+                bidders_badge.resource_address(),
+                self.bidders_badge.address(),
+                "[Claim Fill]: Badge provided is not a valid bidder's badge"
+            );
+            assert_eq!(
+                bidders_badge.amount(), Decimal::one(),
+                "[Claim Fill]: This method requires that exactly one bidder's badge is passed to the method"
+            );
+            assert!(
+                bidders_badge
+                    .as_non_fungible()
+                    .non_fungible::<BidderBadge>()
+                    .data()
+                    .is_winner,
+                "[Claim Fill]: Badge provided is not a winner's badge."
+            );
+
+            // At this point we know that the fill can be claimed.
+            let non_fungible_local_id: NonFungibleLocalId =
+                bidders_badge.as_non_fungible().non_fungible_local_id();
+            let fill: Decimal = self.fill_amount(non_fungible_local_id.clone());
+            self.unclaimed_fills = self.unclaimed_fills.checked_sub(fill).unwrap();
+            let lot: Bucket = self.lot_vault.as_mut().unwrap().take(fill);
+            let unused_funds: Bucket = self
+                .bid_vaults
+                .get_mut(&non_fungible_local_id)
+                .unwrap()
+                .take_all();
+            bidders_badge.burn();
+
+            return (lot, unused_funds);
         }
 
         /// Attempts to transition the state from Open to Settled.
@@ -542,16 +1536,10 @@ mod english_auction {
                     // We would like to either transition to the Settled state if there are people who have placed bids
                     // and we can select a winner, or transition to the canceled state if there are no bids and the NFTs
                     // should be sent back.
-                    if self.has_bids() {
-                        // Determining the NFT ID which corresponds to the largest bid that has been made for this NFT
-                        // bundle.
-                        let non_fungible_local_id: NonFungibleLocalId = self
-                            .bid_vaults
-                            .iter()
-                            .max_by(|a, b| a.1.amount().cmp(&b.1.amount()))
-                            .map(|(k, _v)| k)
-                            .unwrap()
-                            .clone();
+                    self.outcome = self.settlement_outcome();
+                    if let AuctionOutcome::SoldTo { bidder, .. } = &self.outcome {
+                        // The NFT ID which corresponds to the largest bid that has been made for this NFT bundle.
+                        let non_fungible_local_id: NonFungibleLocalId = bidder.local_id().clone();
 
                         // Update the bidder's badge associated with the above non-fungible id to reflect that this is
                         // the winner of the bid.
@@ -564,17 +1552,60 @@ mod english_auction {
                             true,
                         );
 
-                        // Take the funds from the winner's vault and put them in the payment vault so that the seller
-                        // can now withdraw them
-                        self.payment_vault.put(
-                            self.bid_vaults
-                                .get_mut(&non_fungible_local_id)
-                                .unwrap()
-                                .take_all(),
+                        // Take the winning bid from the winner's vault and put it in the payment vault so that the
+                        // seller can now withdraw it, minus the settlement fee which goes to the fee vault. If the
+                        // auction is settled early, part of the fee is rebated to the seller. Any excess stays in the
+                        // vault until the winner claims the NFTs.
+                        let winning_bid = self.effective_bid(&non_fungible_local_id);
+                        let mut winning_funds = self
+                            .bid_vaults
+                            .get_mut(&non_fungible_local_id)
+                            .unwrap()
+                            .take(winning_bid);
+                        let settlement_fee = self.rebated_settlement_fee(winning_bid);
+                        self.fee_vault.put(winning_funds.take(settlement_fee));
+                        self.payment_vault.put(winning_funds);
+
+                        self.winning_bid = Some(winning_bid);
+                        self.payout_release_epoch = Some(
+                            Runtime::current_epoch()
+                                .after(self.dispute_window_epochs)
+                                .unwrap(),
                         );
+                        self.state = AuctionState::Settled
+                    } else if let AuctionOutcome::SoldInTranches { amount, .. } = &self.outcome {
+                        let amount: Decimal = *amount;
+
+                        // Every bid which is at least partially filled pays for its fill, which goes to the payment
+                        // vault minus the settlement fee. The filled quantities are kept in the lot vault until the
+                        // winners claim them, and any excess funds stay in their vaults until then.
+                        let mut proceeds: Bucket = Bucket::new(self.accepted_payment_token);
+                        for (non_fungible_local_id, fill, cost) in self.tranche_allocation() {
+                            self.bidders_badge.update_non_fungible_data(
+                                &non_fungible_local_id,
+                                "is_winner",
+                                true,
+                            );
+                            proceeds.put(
+                                self.bid_vaults
+                                    .get_mut(&non_fungible_local_id)
+                                    .unwrap()
+                                    .take(cost),
+                            );
+                            self.fills.insert(non_fungible_local_id, fill);
+                            self.unclaimed_fills = self.unclaimed_fills.checked_add(fill).unwrap();
+                        }
+                        let settlement_fee = self.rebated_settlement_fee(amount);
+                        self.fee_vault.put(proceeds.take(settlement_fee));
+                        self.payment_vault.put(proceeds);
 
+                        self.winning_bid = Some(amount);
+                        self.payout_release_epoch = Some(Runtime::current_epoch());
                         self.state = AuctionState::Settled
                     } else {
+                        // Either nobody bid or the highest bid does not meet the seller's minimum proceeds. In both
+                        // cases the sale is void: the seller may reclaim the NFTs through `cancel_auction` and every
+                        // bidder may withdraw their funds through `cancel_bid`.
                         self.state = AuctionState::Canceled
                     }
                 }
@@ -582,6 +1613,147 @@ mod english_auction {
             }
         }
 
+        /// Settles the auction and pays the settlement reward to the caller.
+        ///
+        /// This method performs the same state transition as `ensure_auction_settlement`, but it is meant to be
+        /// called by keepers who are rewarded for settling auctions promptly. The settlement reward is only paid if
+        /// the auction settles with a winning bid, and only to the first caller as the auction can only settle once.
+        /// If the auction has a dispute window, the reward is held in escrow and paid out when the seller withdraws
+        /// their proceeds, and it is forfeited if the winner is refunded after a dispute.
+        ///
+        /// This method performs a number of checks before the auction is settled:
+        ///
+        /// * **Check 1:** Checks that the auction is still in the `Open` state.
+        /// * **Check 2:** Checks that the ending epoch has been reached.
+        ///
+        /// # Arguments:
+        ///
+        /// * `settler_account` (ComponentAddress) - The account which the settlement reward is deposited into.
+        pub fn settle(&mut self, settler_account: ComponentAddress) {
+            assert!(
+                matches!(self.state, AuctionState::Open),
+                "[Settle]: The auction has already been settled or canceled."
+            );
+            assert!(
+                Runtime::current_epoch() >= self.ending_epoch,
+                "[Settle]: The auction can not be settled before its ending epoch."
+            );
+
+            self.ensure_auction_settlement();
+
+            // The reward was accounted for when checking the seller's proceeds, so the payment vault always covers it.
+            if matches!(self.state, AuctionState::Settled)
+                && self.settlement_reward > Decimal::zero()
+            {
+                if self.dispute_window_epochs > 0 {
+                    self.settler_account = Some(settler_account);
+                    return;
+                }
+
+                let reward: Bucket = self.payment_vault.take(self.settlement_reward);
+                let mut settler_account: Global<Account> = Global::from(settler_account);
+                settler_account.try_deposit_or_abort(reward, None);
+            }
+        }
+
+        /// Allows the winner to dispute the sale, which freezes the seller's proceeds and the NFTs until the dispute
+        /// arbiter resolves the dispute through `resolve_dispute`.
+        ///
+        /// This method performs a number of checks before the dispute is raised:
+        ///
+        /// * **Check 1:** Checks that the auction is settled.
+        /// * **Check 2:** Checks that the badge provided is the winner's badge.
+        /// * **Check 3:** Checks that the sale is not already disputed.
+        /// * **Check 4:** Checks that the dispute window has not yet passed.
+        ///
+        /// # Arguments:
+        ///
+        /// * `winner_badge` (Proof) - A `Proof` of the winner's bidder's badge.
+        pub fn raise_dispute(&mut self, winner_badge: Proof) {
+            // Mandatory call to ensure that the `ensure_auction_settlement` method to ensure that if the conditions are
+            // met, that the auction will proceed to the next stage/state.
+            self.ensure_auction_settlement();
+
+            assert!(
+                matches!(self.state, AuctionState::Settled),
+                "[Raise Dispute]: Only a settled sale can be disputed."
+            );
+            let winner_badge = winner_badge.check(self.bidders_badge.address());
+            assert_eq!(
+                winner_badge.amount(), Decimal::one(),
+                "[Raise Dispute]: This method requires that exactly one bidder's badge is passed to the method"
+            );
+            assert!(
+                winner_badge
+                    .as_non_fungible()
+                    .non_fungible::<BidderBadge>()
+                    .data()
+                    .is_winner,
+                "[Raise Dispute]: Badge provided is not the winner's badge."
+            );
+            assert!(
+                !self.disputed,
+                "[Raise Dispute]: The sale is already disputed."
+            );
+            assert!(
+                Runtime::current_epoch() < self.payout_release_epoch.unwrap(),
+                "[Raise Dispute]: The dispute window has passed."
+            );
+
+            self.disputed = true;
+        }
+
+        /// Resolves a pending dispute.
+        ///
+        /// If the dispute is resolved in favor of the winner, the whole winning bid is returned to the winner's bid:
+        /// the seller's proceeds, the settlement fee and the settlement reward, all of which are held in escrow until
+        /// the dispute window has passed. The sale is voided: the winner may withdraw their funds through `cancel_bid`
+        /// and the seller may reclaim the NFTs through `cancel_auction`. Otherwise, the proceeds are released to the
+        /// seller and the winner may claim the NFTs.
+        ///
+        /// This method performs a number of checks before resolving the dispute:
+        ///
+        /// * **Check 1:** Checks that the sale is disputed.
+        /// * **Check 2:** Checks that a refunded sale was sold to a single winner.
+        ///
+        /// # Arguments:
+        ///
+        /// * `refund_winner` (bool) - Whether the dispute is resolved in favor of the winner.
+        ///
+        /// # Note:
+        ///
+        /// * This is an authenticated method which may only be called by the holder of the dispute arbiter badge.
+        pub fn resolve_dispute(&mut self, refund_winner: bool) {
+            assert!(
+                self.disputed,
+                "[Resolve Dispute]: There is no dispute to resolve."
+            );
+            self.disputed = false;
+
+            if refund_winner {
+                let winner: Option<NonFungibleLocalId> = match &self.outcome {
+                    AuctionOutcome::SoldTo { bidder, .. } => Some(bidder.local_id().clone()),
+                    _ => None,
+                };
+                let winner: NonFungibleLocalId = winner
+                    .expect("[Resolve Dispute]: Only a sale to a single winner can be refunded.");
+
+                // The escrowed settlement reward is part of the payment vault, and is forfeited by the settler.
+                let mut refund: Bucket = self.payment_vault.take_all();
+                refund.put(self.fee_vault.take_all());
+                self.settler_account = None;
+                self.bid_vaults.get_mut(&winner).unwrap().put(refund);
+                self.proxy_bids.remove(&winner);
+                self.bidders_badge
+                    .update_non_fungible_data(&winner, "is_winner", false);
+
+                self.outcome = AuctionOutcome::RefundedAfterDispute;
+                self.state = AuctionState::Canceled;
+            } else {
+                self.payout_release_epoch = Some(Runtime::current_epoch());
+            }
+        }
+
         /// Checks if the NFT bundle has any bids.
         ///
         /// Returns:
@@ -591,6 +1763,448 @@ mod english_auction {
         pub fn has_bids(&self) -> bool {
             return self.bid_vaults.len() > 0;
         }
+
+        /// Returns the minimum bid which would currently lead the auction and win it under the settlement rules.
+        ///
+        /// This is the highest bid raised by the bid increment, but at least the smallest winning bid: the smallest bid
+        /// which gives the seller their minimum proceeds after the settlement fee and reward, which meets the revealed
+        /// reserve, if any, and which is at least the starting price. If no bid can give the seller their minimum
+        /// proceeds, this is `Decimal::MAX`. After the auction has been settled, this is the winning bid. This method
+        /// never panics.
+        pub fn min_next_bid(&self) -> Decimal {
+            if let Some(winning_bid) = self.winning_bid {
+                return winning_bid;
+            }
+
+            let min_winning_bid: Decimal = self.min_winning_bid();
+            if self.has_bids() {
+                return self.raise_bid(self.highest_bid()).max(min_winning_bid);
+            }
+
+            return min_winning_bid;
+        }
+
+        /// Returns the minimum amount of any bid in the auction.
+        pub fn starting_price(&self) -> Decimal {
+            return self.starting_price;
+        }
+
+        /// Returns the outcome of the auction.
+        ///
+        /// Before the ending epoch, an auction which has not been canceled is `Pending`. Once the ending epoch has been
+        /// reached, this returns the outcome which settling the auction leads to, even if nobody has settled it yet.
+        /// This method never panics.
+        pub fn outcome(&self) -> AuctionOutcome {
+            match self.state {
+                AuctionState::Open if Runtime::current_epoch() >= self.ending_epoch => {
+                    self.settlement_outcome()
+                }
+                _ => self.outcome.clone(),
+            }
+        }
+
+        /// Returns the quantity of the lot allocated to the bidder with the given badge when the multi-fill auction
+        /// settled, or zero if their bid was not filled.
+        pub fn fill_amount(&self, bidder: NonFungibleLocalId) -> Decimal {
+            return self.fills.get(&bidder).copied().unwrap_or(Decimal::zero());
+        }
+
+        /// Returns the number of times that a late bid has extended the auction.
+        pub fn extension_count(&self) -> u64 {
+            return self.extension_count;
+        }
+
+        /// Returns the human readable description of the auction provided by the seller.
+        pub fn get_listing_metadata(&self) -> ListingMetadata {
+            return self.listing_metadata.clone();
+        }
+
+        /// Returns the most recent bids made in the auction, oldest first. Increased bids show up as a new record with
+        /// the new total amount of the bid. Proxy bids show up with the amount bid on behalf of the bidder, never with
+        /// their maximum.
+        pub fn bid_history(&self) -> Vec<BidRecord> {
+            return self.bid_history.clone();
+        }
+
+        /// Appends a bid to the bid history, dropping the oldest bid if the history is full.
+        fn record_bid(&mut self, non_fungible_local_id: NonFungibleLocalId, amount: Decimal) {
+            if self.bid_history.len() == MAX_BID_HISTORY_LENGTH {
+                self.bid_history.remove(0);
+            }
+            self.bid_history.push(BidRecord {
+                bidder: NonFungibleGlobalId::new(
+                    self.bidders_badge.address(),
+                    non_fungible_local_id,
+                ),
+                amount,
+                epoch: Runtime::current_epoch(),
+            });
+        }
+
+        /// Swaps the seller's proceeds into the payout token through the configured DEX, if there is a payout
+        /// conversion. Panics if the swap pays out less than the minimum payout.
+        fn convert_payout(&self, payment: Bucket) -> Bucket {
+            let payout_conversion: &PayoutConversion = match &self.payout_conversion {
+                Some(payout_conversion) => payout_conversion,
+                None => return payment,
+            };
+
+            let dex: Global<AnyComponent> = Global::from(payout_conversion.dex);
+            let payout: Bucket = dex.call_raw("swap", scrypto_args!(payment));
+            assert_eq!(
+                payout.resource_address(),
+                payout_conversion.payout_token,
+                "[Withdraw Payment]: The DEX paid out the wrong token."
+            );
+            assert!(
+                payout.amount() >= payout_conversion.min_payout,
+                "[Withdraw Payment]: Slippage too high: the swap returned {} but at least {} was required.",
+                payout.amount(),
+                payout_conversion.min_payout
+            );
+
+            return payout;
+        }
+
+        /// Pushes the escrowed funds of every outbid bidder with a refund account to that account, if the auction pushes
+        /// refunds. A refund which the account rejects is kept in the bidder's vault, where it may be withdrawn through
+        /// `cancel_bid`.
+        fn push_outbid_refunds(&mut self) {
+            if !self.push_refunds {
+                return;
+            }
+
+            let highest_bid: Decimal = self.highest_bid();
+            let outbid_bidders: Vec<(NonFungibleLocalId, ComponentAddress)> = self
+                .refund_accounts
+                .iter()
+                .filter(|(id, _)| {
+                    !self.proxy_bids.contains_key(*id) && self.effective_bid(id) < highest_bid
+                })
+                .map(|(id, refund_account)| (id.clone(), *refund_account))
+                .collect();
+
+            for (id, refund_account) in outbid_bidders.into_iter() {
+                let funds: Bucket = self.bid_vaults.get_mut(&id).unwrap().take_all();
+                let mut refund_account: Global<Account> = Global::from(refund_account);
+                match refund_account.try_deposit_or_refund(funds, None) {
+                    Some(rejected_funds) => {
+                        self.bid_vaults.get_mut(&id).unwrap().put(rejected_funds);
+                    }
+                    None => {
+                        self.bidders_badge.update_non_fungible_data(
+                            &id,
+                            "bid_amount",
+                            Decimal::zero(),
+                        );
+                        self.refund_accounts.remove(&id);
+                    }
+                }
+            }
+        }
+
+        /// Deposits the given bucket into the account of the beneficiary, if one has been set. Returns the bucket if there
+        /// is no beneficiary or if their account rejects it, so that it goes to the caller instead.
+        fn deposit_to_beneficiary(&self, bucket: Bucket) -> Option<Bucket> {
+            match self.beneficiary {
+                Some(beneficiary) => {
+                    let mut beneficiary: Global<Account> = Global::from(beneficiary);
+                    beneficiary.try_deposit_or_refund(bucket, None)
+                }
+                None => Some(bucket),
+            }
+        }
+
+        /// Extends the auction if a bid was placed within `EXTENSION_WINDOW_EPOCHS` of the ending epoch and the auction
+        /// has not yet been extended `max_extensions` times.
+        fn extend_if_late_bid(&mut self) {
+            let current_epoch: Epoch = Runtime::current_epoch();
+            if self.extension_count >= self.max_extensions
+                || current_epoch.after(EXTENSION_WINDOW_EPOCHS).unwrap() <= self.ending_epoch
+            {
+                return;
+            }
+
+            self.ending_epoch = current_epoch.after(EXTENSION_WINDOW_EPOCHS).unwrap();
+            self.extension_count += 1;
+            Runtime::emit_event(AuctionExtendedEvent {
+                ending_epoch: self.ending_epoch,
+                extension_count: self.extension_count,
+            });
+        }
+
+        /// Returns the outcome of settling the auction with the current bids: the highest bid wins if it leaves the
+        /// seller their minimum proceeds after the settlement fee and reward.
+        fn settlement_outcome(&self) -> AuctionOutcome {
+            if !self.has_bids() {
+                return AuctionOutcome::UnsoldNoBids;
+            }
+
+            if self.multi_fill {
+                let allocation = self.tranche_allocation();
+                let sold: Decimal = allocation
+                    .iter()
+                    .fold(Decimal::zero(), |sold, (_, fill, _)| {
+                        sold.checked_add(*fill).unwrap()
+                    });
+                let amount: Decimal = allocation
+                    .iter()
+                    .fold(Decimal::zero(), |amount, (_, _, cost)| {
+                        amount.checked_add(*cost).unwrap()
+                    });
+                let seller_proceeds: Decimal = amount
+                    .checked_sub(self.settlement_fee(amount))
+                    .unwrap()
+                    .checked_sub(self.settlement_reward)
+                    .unwrap();
+                if seller_proceeds < self.min_seller_proceeds
+                    || !self.meets_revealed_reserve(amount)
+                {
+                    return AuctionOutcome::UnsoldReserveNotMet;
+                }
+                return AuctionOutcome::SoldInTranches { sold, amount };
+            }
+
+            let highest_bid: Decimal = self.highest_bid();
+            let seller_proceeds: Decimal = highest_bid
+                .checked_sub(self.settlement_fee(highest_bid))
+                .unwrap()
+                .checked_sub(self.settlement_reward)
+                .unwrap();
+            if seller_proceeds < self.min_seller_proceeds
+                || !self.meets_revealed_reserve(highest_bid)
+            {
+                return AuctionOutcome::UnsoldReserveNotMet;
+            }
+
+            let non_fungible_local_id: NonFungibleLocalId = self
+                .bid_vaults
+                .keys()
+                .max_by(|a, b| self.effective_bid(a).cmp(&self.effective_bid(b)))
+                .unwrap()
+                .clone();
+            return AuctionOutcome::SoldTo {
+                bidder: NonFungibleGlobalId::new(
+                    self.bidders_badge.address(),
+                    non_fungible_local_id,
+                ),
+                amount: highest_bid,
+            };
+        }
+
+        /// Returns the smallest bid which wins the auction under the settlement rules of `settlement_outcome`, rounded
+        /// up to the divisibility of the payment token, or `Decimal::MAX` if there is no such bid.
+        fn min_winning_bid(&self) -> Decimal {
+            // Grossing up the seller's minimum proceeds and the settlement reward by the settlement fee. With a
+            // settlement fee of 100 percent, only a seller who asks for nothing can be paid.
+            let required: Decimal = self
+                .min_seller_proceeds
+                .checked_add(self.settlement_reward)
+                .unwrap_or(Decimal::MAX);
+            let seller_share: Decimal = dec!(100)
+                .checked_sub(self.settlement_fee_percent)
+                .unwrap_or(Decimal::zero());
+            let min_proceeds_bid: Decimal = if required == Decimal::zero() {
+                Decimal::zero()
+            } else if seller_share == Decimal::zero() {
+                Decimal::MAX
+            } else {
+                required
+                    .checked_mul(100)
+                    .and_then(|amount| amount.checked_div(seller_share))
+                    .and_then(|amount| {
+                        amount.checked_round(
+                            Self::divisibility(self.accepted_payment_token),
+                            RoundingMode::AwayFromZero,
+                        )
+                    })
+                    .unwrap_or(Decimal::MAX)
+            };
+
+            return min_proceeds_bid
+                .max(self.revealed_reserve.unwrap_or(Decimal::zero()))
+                .max(self.starting_price);
+        }
+
+        /// Checks whether the given winning bid meets the seller's hidden reserve, which only applies once revealed.
+        fn meets_revealed_reserve(&self, winning_bid: Decimal) -> bool {
+            return self
+                .revealed_reserve
+                .map_or(true, |reserve| winning_bid >= reserve);
+        }
+
+        /// Panics if the given bid amount has more decimal places than the divisibility of the accepted payment token
+        /// allows, as the escrow could not hold such a bid exactly.
+        fn assert_bid_precision(&self, amount: Decimal) {
+            let divisibility: u8 = Self::divisibility(self.accepted_payment_token);
+            assert!(
+                amount.checked_round(divisibility, RoundingMode::ToZero) == Some(amount),
+                "bid precision exceeds token divisibility"
+            );
+        }
+
+        /// Returns how the lot of a multi-fill auction is allocated to the current bids as `(bidder, fill, cost)`
+        /// tuples. Bids are filled in order of their price per unit, highest first, and among bids of the same price
+        /// in the order in which they were placed. The marginal bid pays for its fill at its price per unit, rounded
+        /// down to the divisibility of the payment token.
+        fn tranche_allocation(&self) -> Vec<(NonFungibleLocalId, Decimal, Decimal)> {
+            let mut bids: Vec<(NonFungibleLocalId, Decimal, Decimal, Decimal, u64)> = self
+                .tranche_bids
+                .iter()
+                .map(|(id, tranche_bid)| {
+                    let funds: Decimal = self.bid_vaults.get(id).unwrap().amount();
+                    let unit_price: Decimal = funds.checked_div(tranche_bid.quantity).unwrap();
+                    (
+                        id.clone(),
+                        tranche_bid.quantity,
+                        funds,
+                        unit_price,
+                        tranche_bid.sequence,
+                    )
+                })
+                .collect();
+            bids.sort_by(|a, b| b.3.cmp(&a.3).then(a.4.cmp(&b.4)));
+
+            let payment_divisibility: u8 = Self::divisibility(self.accepted_payment_token);
+            let mut remaining: Decimal = self
+                .lot_vault
+                .as_ref()
+                .map_or(Decimal::zero(), |lot_vault| lot_vault.amount());
+            let mut allocation: Vec<(NonFungibleLocalId, Decimal, Decimal)> = Vec::new();
+            for (id, quantity, funds, _, _) in bids.into_iter() {
+                if remaining == Decimal::zero() {
+                    break;
+                }
+
+                let fill: Decimal = quantity.min(remaining);
+                let cost: Decimal = if fill == quantity {
+                    funds
+                } else {
+                    funds
+                        .checked_mul(fill)
+                        .unwrap()
+                        .checked_div(quantity)
+                        .unwrap()
+                        .checked_round(payment_divisibility, RoundingMode::ToZero)
+                        .unwrap()
+                };
+                remaining = remaining.checked_sub(fill).unwrap();
+                allocation.push((id, fill, cost));
+            }
+
+            return allocation;
+        }
+
+        /// Returns the settlement fee kept from the given winning bid if the auction settles now. If the auction is
+        /// settled early, part of the fee is rebated to the seller.
+        fn rebated_settlement_fee(&self, winning_bid: Decimal) -> Decimal {
+            let settlement_fee: Decimal = self.settlement_fee(winning_bid);
+            if Runtime::current_epoch().number()
+                > self
+                    .ending_epoch
+                    .number()
+                    .saturating_add(self.settlement_grace_epochs)
+            {
+                return settlement_fee;
+            }
+
+            let rebate: Decimal = settlement_fee
+                .checked_mul(self.early_settlement_rebate_percent)
+                .unwrap()
+                .checked_div(100)
+                .unwrap();
+            return settlement_fee.checked_sub(rebate).unwrap();
+        }
+
+        /// Returns the given bid raised by the bid increment, rounded up to the divisibility of the payment token, or
+        /// `Decimal::MAX` if that overflows.
+        fn raise_bid(&self, bid: Decimal) -> Decimal {
+            let increment: Option<Decimal> = match self.bid_increment {
+                BidIncrement::Absolute(amount) => Some(amount),
+                BidIncrement::Percentage(percent) => bid
+                    .checked_mul(percent)
+                    .and_then(|amount| amount.checked_div(100)),
+            };
+            return increment
+                .and_then(|increment| bid.checked_add(increment))
+                .and_then(|amount| {
+                    amount.checked_round(
+                        Self::divisibility(self.accepted_payment_token),
+                        RoundingMode::AwayFromZero,
+                    )
+                })
+                .unwrap_or(Decimal::MAX);
+        }
+
+        /// Returns the divisibility of the given resource, which is zero for non-fungible resources.
+        fn divisibility(resource_address: ResourceAddress) -> u8 {
+            match ResourceManager::from_address(resource_address).resource_type() {
+                ResourceType::Fungible { divisibility } => divisibility,
+                ResourceType::NonFungible { .. } => DIVISIBILITY_NONE,
+            }
+        }
+
+        /// Returns the settlement fee kept from the given winning bid.
+        fn settlement_fee(&self, winning_bid: Decimal) -> Decimal {
+            return winning_bid
+                .checked_mul(self.settlement_fee_percent)
+                .unwrap()
+                .checked_div(100)
+                .unwrap();
+        }
+
+        /// Returns the highest effective bid placed on the NFT bundle, or zero if there are no bids.
+        fn highest_bid(&self) -> Decimal {
+            return self
+                .bid_vaults
+                .keys()
+                .map(|non_fungible_local_id| self.effective_bid(non_fungible_local_id))
+                .max()
+                .unwrap_or(Decimal::zero());
+        }
+
+        /// Returns the effective bid of the bidder with the given badge. For proxy bids this is the amount the
+        /// auction has bid on their behalf, for all other bids it is the amount of funds escrowed.
+        fn effective_bid(&self, non_fungible_local_id: &NonFungibleLocalId) -> Decimal {
+            match self.proxy_bids.get(non_fungible_local_id) {
+                Some(proxy_bid) => proxy_bid.current_amount,
+                None => self.bid_vaults.get(non_fungible_local_id).unwrap().amount(),
+            }
+        }
+
+        /// Advances every proxy bid to the lowest amount that beats all competing bids, up to its maximum.
+        ///
+        /// A competing proxy bid could bid up to its own maximum, so it competes with its maximum rather than with
+        /// its current effective bid.
+        fn update_proxy_bids(&mut self) {
+            let highest_manual_bid: Decimal = self
+                .bid_vaults
+                .iter()
+                .filter(|(id, _)| !self.proxy_bids.contains_key(*id))
+                .map(|(_, vault)| vault.amount())
+                .max()
+                .unwrap_or(Decimal::zero());
+
+            let proxy_maximums: Vec<(NonFungibleLocalId, Decimal)> = self
+                .proxy_bids
+                .iter()
+                .map(|(id, proxy_bid)| (id.clone(), proxy_bid.max_amount))
+                .collect();
+
+            for (id, max_amount) in proxy_maximums.iter() {
+                let highest_competing_bid: Decimal = proxy_maximums
+                    .iter()
+                    .filter(|(other_id, _)| other_id != id)
+                    .map(|(_, other_max_amount)| *other_max_amount)
+                    .fold(highest_manual_bid, |highest, amount| highest.max(amount));
+
+                let current_amount: Decimal = self
+                    .raise_bid(highest_competing_bid)
+                    .max(self.starting_price)
+                    .min(*max_amount);
+                self.proxy_bids.get_mut(id).unwrap().current_amount = current_amount;
+            }
+        }
     }
 }
 
@@ -599,7 +2213,7 @@ mod english_auction {
 #[derive(NonFungibleData, ScryptoSbor)]
 struct BidderBadge {
     /// A mutable decimal which holds information on the amount of funds that this bidder has bid. This is mutable as
-    /// bidders are allowed to add to their bid.
+    /// bidders are allowed to add to their bid. For proxy bids, this is the maximum bid.
     #[mutable]
     bid_amount: Decimal,
 
@@ -608,6 +2222,177 @@ struct BidderBadge {
     is_winner: bool,
 }
 
+/// The state of a proxy bid, where the auction bids on behalf of the bidder up to a maximum amount.
+#[derive(Debug, ScryptoSbor)]
+struct ProxyBid {
+    /// The maximum amount that the auction may bid on behalf of the bidder. This is the amount that is escrowed.
+    max_amount: Decimal,
+
+    /// The amount currently bid on behalf of the bidder.
+    current_amount: Decimal,
+}
+
+/// The quantity of the lot that a bidder of a multi-fill auction bid for.
+#[derive(Debug, ScryptoSbor)]
+struct TrancheBid {
+    /// The quantity of the lot bid for. The bidder's escrowed funds pay for all of it.
+    quantity: Decimal,
+
+    /// The number of tranche bids placed before this one, which breaks ties between bids of the same price.
+    sequence: u64,
+}
+
+/// The human readable description of an auction, intended for UIs.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct ListingMetadata {
+    /// The title of the auction listing.
+    pub title: String,
+
+    /// The description of the auction listing.
+    pub description: String,
+
+    /// An optional URL of an image to display with the auction listing.
+    pub image_url: Option<String>,
+}
+
+/// The configuration of an auction, which holds its optional features and its listing metadata.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct AuctionConfig {
+    /// The minimum amount that the seller must receive from the winning bid after the settlement fee. If the winning
+    /// bid does not meet it, the sale is voided when the auction settles.
+    pub min_seller_proceeds: Decimal,
+
+    /// The percentage of the winning bid which is kept as a settlement fee.
+    pub settlement_fee_percent: Decimal,
+
+    /// The badge of whoever collects the settlement fees through `withdraw_fees`. Required if there is a settlement
+    /// fee.
+    pub fee_collector: Option<ResourceAddress>,
+
+    /// The human readable description of the auction listing.
+    pub listing_metadata: ListingMetadata,
+
+    /// Whether outbid bidders who provided a refund account get their escrowed funds pushed to that account as soon as
+    /// they are outbid.
+    pub push_refunds: bool,
+
+    /// The maximum number of times that late bids may extend the auction.
+    pub max_extensions: u64,
+
+    /// The amount of the winning bid paid to whoever settles the auction through `settle`.
+    pub settlement_reward: Decimal,
+
+    /// The minimum amount of any bid, including the first one.
+    pub starting_price: Decimal,
+
+    /// The number of epochs after the ending epoch within which the auction counts as settled early.
+    pub settlement_grace_epochs: u64,
+
+    /// The percentage of the settlement fee which goes to the seller instead if the auction is settled early.
+    pub early_settlement_rebate_percent: Decimal,
+
+    /// The number of epochs after settlement during which the winner may dispute the sale. The seller's proceeds are
+    /// held in escrow until the window has passed. Zero disables disputes.
+    pub dispute_window_epochs: u64,
+
+    /// The badge of the arbiter who resolves disputes.
+    pub dispute_arbiter: Option<ResourceAddress>,
+
+    /// If set, the seller's proceeds are swapped into the payout token through the given DEX when they are withdrawn.
+    pub payout_conversion: Option<PayoutConversion>,
+
+    /// Whether the auction sells a fungible lot, deposited through `deposit_lot`, to multiple winners instead of the
+    /// NFTs to a single winner.
+    pub multi_fill: bool,
+
+    /// The SHA-256 hash of the SBOR encoded `(reserve, nonce)` tuple of a hidden reserve, where the reserve is a
+    /// `Decimal` and the nonce a `String`.
+    pub reserve_commitment: Option<[u8; 32]>,
+
+    /// The account whose owner authorizes the seller only methods instead of an ownership badge.
+    pub seller_account: Option<ComponentAddress>,
+
+    /// How much a new bid must exceed the highest bid by, which is also how much proxy bids are raised above the
+    /// highest competing bid.
+    pub bid_increment: BidIncrement,
+}
+
+/// How much a new bid must exceed the highest bid by.
+#[derive(Debug, Clone, Copy, ScryptoSbor)]
+pub enum BidIncrement {
+    /// A fixed amount of the payment token.
+    Absolute(Decimal),
+
+    /// A percentage of the highest bid.
+    Percentage(Decimal),
+}
+
+/// The conversion of the seller's proceeds into another token when they are withdrawn.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct PayoutConversion {
+    /// The token which the seller wishes to be paid in.
+    pub payout_token: ResourceAddress,
+
+    /// The DEX component whose `swap` method converts the proceeds into the payout token.
+    pub dex: ComponentAddress,
+
+    /// The minimum amount of the payout token that the seller accepts for their proceeds.
+    pub min_payout: Decimal,
+}
+
+/// A single entry of the bid history of an auction.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct BidRecord {
+    /// The global id of the bidder's badge that made the bid.
+    pub bidder: NonFungibleGlobalId,
+
+    /// The total amount of the bidder's bid after this bid was made. For proxy bids, this is the amount bid on behalf
+    /// of the bidder at the time.
+    pub amount: Decimal,
+
+    /// The epoch in which the bid was made.
+    pub epoch: Epoch,
+}
+
+/// The event emitted when a late bid extends the auction.
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct AuctionExtendedEvent {
+    /// The new ending epoch of the auction.
+    ending_epoch: Epoch,
+
+    /// The number of times that the auction has been extended, including this extension.
+    extension_count: u64,
+}
+
+/// The definitive outcome of an auction, intended for external systems which poll auctions.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub enum AuctionOutcome {
+    /// The auction is still running.
+    Pending,
+
+    /// The NFTs were sold to the holder of the given bidder's badge for the given winning bid.
+    SoldTo {
+        bidder: NonFungibleGlobalId,
+        amount: Decimal,
+    },
+
+    /// The lot of a multi-fill auction was sold to the highest bids. `sold` is the quantity of the lot which was
+    /// allocated and `amount` is the total which the filled bids pay for it.
+    SoldInTranches { sold: Decimal, amount: Decimal },
+
+    /// The auction ended without any bids.
+    UnsoldNoBids,
+
+    /// The auction ended, but the highest bid did not give the seller their minimum proceeds.
+    UnsoldReserveNotMet,
+
+    /// The seller canceled the auction before it ended.
+    CanceledBySeller,
+
+    /// The NFTs were sold, but the winner disputed the sale and was refunded.
+    RefundedAfterDispute,
+}
+
 /// The English auction is by definition stateful and during different periods and states of the auction different
 /// actions may be allowed or disallowed. This enum describes the state of the English auction component.
 #[derive(Debug, ScryptoSbor)]
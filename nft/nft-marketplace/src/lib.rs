@@ -4,5 +4,8 @@ mod dutch_auction;
 mod english_auction;
 mod fixed_price_sale;
 
+/// A curated marketplace which restricts the NFT collections that may be auctioned through it.
+mod marketplace;
+
 /// A bootstrap module with a blueprint that creates some test NFTs for us to use when testing the blueprints.
 mod bootstrap;
@@ -0,0 +1,124 @@
+use crate::english_auction::english_auction::EnglishAuction;
+use crate::english_auction::AuctionConfig;
+use scrypto::prelude::*;
+
+#[blueprint]
+mod marketplace {
+    // Setting up the access rules for the component methods such that only the holder of the admin badge can curate
+    // the collections which may be sold on the marketplace.
+    enable_method_auth! {
+        roles {
+            admin => updatable_by: [];
+        },
+        methods {
+            add_allowed_collection => restrict_to: [admin];
+            remove_allowed_collection => restrict_to: [admin];
+            instantiate_english_auction => PUBLIC;
+            is_collection_allowed => PUBLIC;
+        }
+    }
+    /// This blueprint defines a curated marketplace. Sellers who list their NFTs through a marketplace component may
+    /// only auction NFTs from the collections which the marketplace admin has approved.
+    ///
+    /// The sale blueprints may still be instantiated directly without going through a marketplace, in which case no
+    /// allowlist applies.
+    struct Marketplace {
+        /// The resource addresses of the NFT collections which may be sold through this marketplace.
+        allowed_collections: HashSet<ResourceAddress>,
+    }
+
+    impl Marketplace {
+        /// Instantiates a new curated marketplace.
+        ///
+        /// # Arguments:
+        ///
+        /// * `allowed_collections` (Vec<ResourceAddress>) - The NFT collections which are approved from the start.
+        ///
+        /// # Returns:
+        ///
+        /// This function returns a tuple which has the following format:
+        /// * `Global<Marketplace>` - A Global<Marketplace> component object of the instantiated `Marketplace`
+        /// component.
+        /// * `Bucket` - A bucket containing the admin badge which is used to curate the allowed collections.
+        pub fn instantiate_marketplace(
+            allowed_collections: Vec<ResourceAddress>,
+        ) -> (Global<Marketplace>, FungibleBucket) {
+            let admin_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata!(
+                    init {
+                        "name" => "Marketplace Admin Badge".to_owned(), locked;
+                        "description" =>
+                        "An admin badge used to curate the collections sold on the marketplace.".to_owned(), locked;
+                        "symbol" => "ADMIN".to_owned(), locked;
+                    }
+                ))
+                .mint_initial_supply(1);
+
+            let marketplace = Self {
+                allowed_collections: allowed_collections.into_iter().collect(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .roles(roles!(
+                admin => rule!(require(admin_badge.resource_address()));
+            ))
+            .globalize();
+
+            return (marketplace, admin_badge);
+        }
+
+        /// Approves the given NFT collection for sale on the marketplace.
+        ///
+        /// # Note:
+        ///
+        /// * This is an authenticated method which may only be called by the holder of the admin badge.
+        pub fn add_allowed_collection(&mut self, collection: ResourceAddress) {
+            self.allowed_collections.insert(collection);
+        }
+
+        /// Removes the approval of the given NFT collection. Auctions which are already running are not affected.
+        ///
+        /// # Note:
+        ///
+        /// * This is an authenticated method which may only be called by the holder of the admin badge.
+        pub fn remove_allowed_collection(&mut self, collection: ResourceAddress) {
+            self.allowed_collections.remove(&collection);
+        }
+
+        /// Checks if the given NFT collection may be sold on the marketplace.
+        pub fn is_collection_allowed(&self, collection: ResourceAddress) -> bool {
+            return self.allowed_collections.contains(&collection);
+        }
+
+        /// Instantiates a new english auction for the passed NFTs through the marketplace.
+        ///
+        /// This method performs a single check before the auction is instantiated:
+        ///
+        /// * **Check 1:** Checks that all of the NFTs belong to collections approved by the marketplace.
+        ///
+        /// All other arguments, checks, and return values are the same as those of
+        /// `EnglishAuction::instantiate_english_auction`.
+        pub fn instantiate_english_auction(
+            &self,
+            non_fungible_tokens: Vec<NonFungibleBucket>,
+            accepted_payment_token: ResourceAddress,
+            relative_ending_epoch: u64,
+            config: AuctionConfig,
+        ) -> (Global<EnglishAuction>, Option<FungibleBucket>) {
+            assert!(
+                non_fungible_tokens
+                    .iter()
+                    .all(|bucket| self.is_collection_allowed(bucket.resource_address())),
+                "[Marketplace]: Can not auction NFTs of a collection not approved by this marketplace."
+            );
+
+            return EnglishAuction::instantiate_english_auction(
+                non_fungible_tokens,
+                accepted_payment_token,
+                relative_ending_epoch,
+                config,
+            );
+        }
+    }
+}
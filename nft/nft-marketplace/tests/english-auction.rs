@@ -1,16 +1,143 @@
 use radix_engine::transaction::TransactionReceipt;
 use scrypto::prelude::*;
 use scrypto_unit::*;
+use sha2::{Digest, Sha256};
 use transaction::{
     builder::ManifestBuilder, manifest::decompiler::ManifestObjectNames,
     prelude::TransactionManifestV1,
 };
 
+/// The package of the mock components, which is published next to this package where a test needs them.
+const TEST_MOCKS_PACKAGE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../shared/test-mocks");
+
 pub struct Account {
     public_key: Secp256k1PublicKey,
     account_address: ComponentAddress,
 }
 
+/// The addresses of an instantiated english auction and of the badges it created.
+pub struct EnglishAuctionAddresses {
+    component: ComponentAddress,
+    ownership_badge: ResourceAddress,
+    bidders_badge: ResourceAddress,
+}
+
+/// The arguments used to instantiate an english auction, other than the NFTs being sold.
+pub struct AuctionArguments {
+    accepted_payment_token: ResourceAddress,
+    relative_ending_epoch: u64,
+    min_seller_proceeds: Decimal,
+    settlement_fee_percent: Decimal,
+    fee_collector: Option<ResourceAddress>,
+    title: String,
+    description: String,
+    image_url: Option<String>,
+    push_refunds: bool,
+    max_extensions: u64,
+    settlement_reward: Decimal,
+    starting_price: Decimal,
+    settlement_grace_epochs: u64,
+    early_settlement_rebate_percent: Decimal,
+    dispute_window_epochs: u64,
+    dispute_arbiter: Option<ResourceAddress>,
+    payout_conversion: Option<PayoutConversion>,
+    multi_fill: bool,
+    reserve_commitment: Option<[u8; 32]>,
+    seller_account: Option<ComponentAddress>,
+    bid_increment: BidIncrement,
+}
+
+impl Default for AuctionArguments {
+    fn default() -> Self {
+        Self {
+            accepted_payment_token: XRD,
+            relative_ending_epoch: 10,
+            min_seller_proceeds: dec!(0),
+            settlement_fee_percent: dec!(0),
+            fee_collector: None,
+            title: "Test auction".to_string(),
+            description: String::new(),
+            image_url: None,
+            push_refunds: false,
+            max_extensions: 0,
+            settlement_reward: dec!(0),
+            starting_price: dec!(0),
+            settlement_grace_epochs: 0,
+            early_settlement_rebate_percent: dec!(0),
+            dispute_window_epochs: 0,
+            dispute_arbiter: None,
+            payout_conversion: None,
+            multi_fill: false,
+            reserve_commitment: None,
+            seller_account: None,
+            bid_increment: BidIncrement::Absolute(dec!(1)),
+        }
+    }
+}
+
+/// The configuration of an auction, mirroring the `AuctionConfig` of the blueprint.
+#[derive(ManifestSbor)]
+pub struct AuctionConfig {
+    min_seller_proceeds: Decimal,
+    settlement_fee_percent: Decimal,
+    fee_collector: Option<ResourceAddress>,
+    listing_metadata: ListingMetadata,
+    push_refunds: bool,
+    max_extensions: u64,
+    settlement_reward: Decimal,
+    starting_price: Decimal,
+    settlement_grace_epochs: u64,
+    early_settlement_rebate_percent: Decimal,
+    dispute_window_epochs: u64,
+    dispute_arbiter: Option<ResourceAddress>,
+    payout_conversion: Option<PayoutConversion>,
+    multi_fill: bool,
+    reserve_commitment: Option<[u8; 32]>,
+    seller_account: Option<ComponentAddress>,
+    bid_increment: BidIncrement,
+}
+
+/// How much a new bid must exceed the highest bid by, mirroring the `BidIncrement` of the blueprint.
+#[derive(ManifestSbor)]
+pub enum BidIncrement {
+    Absolute(Decimal),
+    Percentage(Decimal),
+}
+
+/// The listing metadata of an auction, mirroring the `ListingMetadata` of the blueprint.
+#[derive(ManifestSbor)]
+pub struct ListingMetadata {
+    title: String,
+    description: String,
+    image_url: Option<String>,
+}
+
+/// The conversion of the seller's proceeds, mirroring the `PayoutConversion` of the blueprint.
+#[derive(ManifestSbor)]
+pub struct PayoutConversion {
+    payout_token: ResourceAddress,
+    dex: ComponentAddress,
+    min_payout: Decimal,
+}
+
+/// The outcome of an auction, mirroring the `AuctionOutcome` of the blueprint.
+#[derive(ScryptoSbor, Debug, PartialEq, Eq)]
+pub enum AuctionOutcome {
+    Pending,
+    SoldTo {
+        bidder: NonFungibleGlobalId,
+        amount: Decimal,
+    },
+    SoldInTranches {
+        sold: Decimal,
+        amount: Decimal,
+    },
+    UnsoldNoBids,
+    UnsoldReserveNotMet,
+    CanceledBySeller,
+    RefundedAfterDispute,
+}
+
 pub struct TestEnvironment {
     test_runner: DefaultTestRunner,
     account: Account,
@@ -39,12 +166,33 @@ impl TestEnvironment {
         }
     }
 
+    pub fn new_account(&mut self) -> Account {
+        let (public_key, _private_key, account_address) = self.test_runner.new_allocated_account();
+
+        Account {
+            public_key,
+            account_address,
+        }
+    }
+
     pub fn execute_manifest_ignoring_fee(
         &mut self,
         manifest_names: ManifestObjectNames,
         manifest: TransactionManifestV1,
         name: &str,
         network: &NetworkDefinition,
+    ) -> TransactionReceipt {
+        let public_key = self.account.public_key;
+        self.execute_manifest_ignoring_fee_as(public_key, manifest_names, manifest, name, network)
+    }
+
+    pub fn execute_manifest_ignoring_fee_as(
+        &mut self,
+        public_key: Secp256k1PublicKey,
+        manifest_names: ManifestObjectNames,
+        manifest: TransactionManifestV1,
+        name: &str,
+        network: &NetworkDefinition,
     ) -> TransactionReceipt {
         dump_manifest_to_file_system(
             manifest_names,
@@ -57,17 +205,14 @@ impl TestEnvironment {
 
         self.test_runner.execute_manifest_ignoring_fee(
             manifest,
-            vec![NonFungibleGlobalId::from_public_key(
-                &self.account.public_key,
-            )],
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
         )
     }
 
     pub fn instantiate_english_auction(
         &mut self,
         non_fungible_tokens: ResourceAddress,
-        accepted_payment_token: ResourceAddress,
-        relative_ending_epoch: u64,
+        arguments: AuctionArguments,
     ) -> TransactionReceipt {
         let manifest = ManifestBuilder::new()
             .withdraw_non_fungibles_from_account(
@@ -75,16 +220,95 @@ impl TestEnvironment {
                 non_fungible_tokens,
                 btreeset!(NonFungibleLocalId::integer(1)),
             )
-            .take_all_from_worktop(non_fungible_tokens, "bucket")
+            .take_all_from_worktop(non_fungible_tokens, "bucket");
+
+        self.call_instantiate_english_auction(manifest, vec!["bucket"], arguments)
+    }
+
+    /// Instantiates a new multi-fill english auction, deposits `lot_amount` of the seller's `lot_token` as its lot, and
+    /// returns the addresses of the component and its badges.
+    pub fn tranche_auction(
+        &mut self,
+        lot_token: ResourceAddress,
+        lot_amount: Decimal,
+        arguments: AuctionArguments,
+    ) -> EnglishAuctionAddresses {
+        let receipt =
+            self.call_instantiate_english_auction(ManifestBuilder::new(), vec![], arguments);
+        let commit = receipt.expect_commit_success();
+        let english_auction = EnglishAuctionAddresses {
+            component: commit.new_component_addresses()[0],
+            ownership_badge: commit.new_resource_addresses()[0],
+            bidders_badge: commit.new_resource_addresses()[1],
+        };
+
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(
+                self.account.account_address,
+                english_auction.ownership_badge,
+                dec!(1),
+            )
+            .withdraw_from_account(self.account.account_address, lot_token, lot_amount)
+            .take_all_from_worktop(lot_token, "lot")
+            .call_method_with_name_lookup(english_auction.component, "deposit_lot", |lookup| {
+                (lookup.bucket("lot"),)
+            });
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "deposit_lot",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success();
+
+        english_auction
+    }
+
+    /// Calls `instantiate_english_auction` at the end of the given manifest, selling the named buckets of NFTs.
+    fn call_instantiate_english_auction(
+        &mut self,
+        manifest: ManifestBuilder,
+        bucket_names: Vec<&str>,
+        arguments: AuctionArguments,
+    ) -> TransactionReceipt {
+        let manifest = manifest
             .call_function_with_name_lookup(
                 self.package_address,
                 "EnglishAuction",
                 "instantiate_english_auction",
                 |lookup| {
+                    let non_fungible_tokens: Vec<ManifestBucket> = bucket_names
+                        .iter()
+                        .map(|bucket_name| lookup.bucket(*bucket_name))
+                        .collect();
+                    let config = AuctionConfig {
+                        min_seller_proceeds: arguments.min_seller_proceeds,
+                        settlement_fee_percent: arguments.settlement_fee_percent,
+                        fee_collector: arguments.fee_collector,
+                        listing_metadata: ListingMetadata {
+                            title: arguments.title,
+                            description: arguments.description,
+                            image_url: arguments.image_url,
+                        },
+                        push_refunds: arguments.push_refunds,
+                        max_extensions: arguments.max_extensions,
+                        settlement_reward: arguments.settlement_reward,
+                        starting_price: arguments.starting_price,
+                        settlement_grace_epochs: arguments.settlement_grace_epochs,
+                        early_settlement_rebate_percent: arguments.early_settlement_rebate_percent,
+                        dispute_window_epochs: arguments.dispute_window_epochs,
+                        dispute_arbiter: arguments.dispute_arbiter,
+                        payout_conversion: arguments.payout_conversion,
+                        multi_fill: arguments.multi_fill,
+                        reserve_commitment: arguments.reserve_commitment,
+                        seller_account: arguments.seller_account,
+                        bid_increment: arguments.bid_increment,
+                    };
                     (
-                        vec![lookup.bucket("bucket")],
-                        accepted_payment_token,
-                        relative_ending_epoch,
+                        non_fungible_tokens,
+                        arguments.accepted_payment_token,
+                        arguments.relative_ending_epoch,
+                        config,
                     )
                 },
             )
@@ -97,19 +321,2845 @@ impl TestEnvironment {
             &NetworkDefinition::simulator(),
         )
     }
-}
 
-#[test]
-fn instantiate_english_auction() {
-    let mut test_environment = TestEnvironment::instantiate_test();
+    /// Instantiates a new english auction and returns the addresses of the component and its badges.
+    pub fn english_auction(
+        &mut self,
+        non_fungible_tokens: ResourceAddress,
+        arguments: AuctionArguments,
+    ) -> EnglishAuctionAddresses {
+        let receipt = self.instantiate_english_auction(non_fungible_tokens, arguments);
+        let commit = receipt.expect_commit_success();
 
-    let non_fungible_token = test_environment
-        .test_runner
-        .create_non_fungible_resource(test_environment.account.account_address);
+        EnglishAuctionAddresses {
+            component: commit.new_component_addresses()[0],
+            ownership_badge: commit.new_resource_addresses()[0],
+            bidders_badge: commit.new_resource_addresses()[1],
+        }
+    }
 
-    let receipt = test_environment.instantiate_english_auction(non_fungible_token, XRD, 10);
+    /// Instantiates a mock DEX holding 1000 of a new token owned by the seller, which it pays out at the given
+    /// `rate`. Returns the addresses of the DEX and of its output token.
+    pub fn instantiate_mock_dex(&mut self, rate: Decimal) -> (ComponentAddress, ResourceAddress) {
+        let output_token =
+            self.test_runner
+                .create_fungible_resource(dec!(1000), 18, self.account.account_address);
+        let mocks_package_address = self.test_runner.compile_and_publish(TEST_MOCKS_PACKAGE);
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account.account_address, output_token, dec!(1000))
+            .take_all_from_worktop(output_token, "output_tokens")
+            .call_function_with_name_lookup(
+                mocks_package_address,
+                "MockDex",
+                "instantiate_mock_dex",
+                |lookup| (lookup.bucket("output_tokens"), rate),
+            );
+        let dex = self
+            .execute_manifest_ignoring_fee(
+                manifest.object_names(),
+                manifest.build(),
+                "instantiate_mock_dex",
+                &NetworkDefinition::simulator(),
+            )
+            .expect_commit_success()
+            .new_component_addresses()[0];
 
-    receipt.expect_commit_success();
-}
+        (dex, output_token)
+    }
+
+    pub fn place_bid(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        payment_token: ResourceAddress,
+        amount: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(bidder.account_address, payment_token, amount)
+            .take_all_from_worktop(payment_token, "bucket")
+            .call_method_with_name_lookup(english_auction, "bid", |lookup| {
+                (lookup.bucket("bucket"),)
+            })
+            .deposit_batch(bidder.account_address);
+
+        self.execute_manifest_ignoring_fee_as(
+            bidder.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "bid",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn bid_for_quantity(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        amount: Decimal,
+        quantity: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(bidder.account_address, XRD, amount)
+            .take_all_from_worktop(XRD, "bucket")
+            .call_method_with_name_lookup(english_auction, "bid_for_quantity", |lookup| {
+                (lookup.bucket("bucket"), quantity)
+            })
+            .deposit_batch(bidder.account_address);
+
+        self.execute_manifest_ignoring_fee_as(
+            bidder.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "bid_for_quantity",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn place_bid_with_refund_account(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        amount: Decimal,
+    ) -> TransactionReceipt {
+        let refund_account = bidder.account_address;
+        self.place_bid_refunding_to(bidder, english_auction, amount, refund_account)
+    }
+
+    /// Places a bid whose refunds are pushed to the given address, which need not be the bidder's account.
+    pub fn place_bid_refunding_to(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        amount: Decimal,
+        refund_account: ComponentAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(bidder.account_address, XRD, amount)
+            .take_all_from_worktop(XRD, "bucket")
+            .call_method_with_name_lookup(english_auction, "bid_with_refund_account", |lookup| {
+                (lookup.bucket("bucket"), refund_account)
+            })
+            .deposit_batch(bidder.account_address);
+
+        self.execute_manifest_ignoring_fee_as(
+            bidder.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "bid_with_refund_account",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    /// Makes the account reject all deposits from third parties, such as the auction.
+    pub fn reject_deposits(&mut self, account: &Account) {
+        let manifest = ManifestBuilder::new().call_method(
+            account.account_address,
+            ACCOUNT_SET_DEFAULT_DEPOSIT_RULE_IDENT,
+            manifest_args!(DefaultDepositRule::Reject),
+        );
+
+        self.execute_manifest_ignoring_fee_as(
+            account.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "set_default_deposit_rule",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success();
+    }
+
+    pub fn place_proxy_bid(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        max: Decimal,
+    ) -> TransactionReceipt {
+        self.place_proxy_bid_with(bidder, english_auction, XRD, max, max)
+    }
+
+    /// Places a proxy bid paying `payment` of the given token, which may exceed the maximum bid.
+    pub fn place_proxy_bid_with(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        payment_token: ResourceAddress,
+        max: Decimal,
+        payment: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(bidder.account_address, payment_token, payment)
+            .take_all_from_worktop(payment_token, "bucket")
+            .call_method_with_name_lookup(english_auction, "place_proxy_bid", |lookup| {
+                (max, lookup.bucket("bucket"))
+            })
+            .deposit_batch(bidder.account_address);
+
+        self.execute_manifest_ignoring_fee_as(
+            bidder.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "place_proxy_bid",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    /// Adds `amount` to the bid of the given bidder, presenting a proof of their bidder's badge.
+    pub fn increase_bid(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        bidders_badge: ResourceAddress,
+        amount: Decimal,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(bidder.account_address, bidders_badge, dec!(1))
+            .pop_from_auth_zone("bidders_badge")
+            .withdraw_from_account(bidder.account_address, XRD, amount)
+            .take_all_from_worktop(XRD, "bucket")
+            .call_method_with_name_lookup(english_auction, "increase_bid", |lookup| {
+                (lookup.bucket("bucket"), lookup.proof("bidders_badge"))
+            });
+
+        self.execute_manifest_ignoring_fee_as(
+            bidder.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "increase_bid",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn advance_epochs(&mut self, epochs: u64) {
+        let current_epoch = self.test_runner.get_current_epoch();
+        self.test_runner
+            .set_current_epoch(current_epoch.after(epochs).unwrap());
+    }
+
+    pub fn settle(&mut self, english_auction: ComponentAddress) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new().call_method(
+            english_auction,
+            "ensure_auction_settlement",
+            manifest_args!(),
+        );
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "ensure_auction_settlement",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn settle_for_reward(
+        &mut self,
+        settler: &Account,
+        english_auction: ComponentAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new().call_method(
+            english_auction,
+            "settle",
+            manifest_args!(settler.account_address),
+        );
+
+        self.execute_manifest_ignoring_fee_as(
+            settler.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "settle",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn raise_dispute(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        bidders_badge: ResourceAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(bidder.account_address, bidders_badge, dec!(1))
+            .pop_from_auth_zone("winner_badge")
+            .call_method_with_name_lookup(english_auction, "raise_dispute", |lookup| {
+                (lookup.proof("winner_badge"),)
+            });
+
+        self.execute_manifest_ignoring_fee_as(
+            bidder.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "raise_dispute",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    /// Resolves the dispute as the given account, presenting its `arbiter_badge`.
+    pub fn resolve_dispute(
+        &mut self,
+        arbiter: &Account,
+        english_auction: ComponentAddress,
+        arbiter_badge: ResourceAddress,
+        refund_winner: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(arbiter.account_address, arbiter_badge, dec!(1))
+            .call_method(
+                english_auction,
+                "resolve_dispute",
+                manifest_args!(refund_winner),
+            );
+
+        self.execute_manifest_ignoring_fee_as(
+            arbiter.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "resolve_dispute",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    /// Returns the bid history of the auction as `(bidder, amount, epoch)` tuples.
+    pub fn bid_history(
+        &mut self,
+        english_auction: ComponentAddress,
+    ) -> Vec<(NonFungibleGlobalId, Decimal, Epoch)> {
+        let manifest =
+            ManifestBuilder::new().call_method(english_auction, "bid_history", manifest_args!());
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "bid_history",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success()
+        .output(1)
+    }
+
+    pub fn extension_count(&mut self, english_auction: ComponentAddress) -> u64 {
+        let manifest = ManifestBuilder::new().call_method(
+            english_auction,
+            "extension_count",
+            manifest_args!(),
+        );
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "extension_count",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success()
+        .output(1)
+    }
+
+    pub fn min_next_bid(&mut self, english_auction: ComponentAddress) -> Decimal {
+        let manifest =
+            ManifestBuilder::new().call_method(english_auction, "min_next_bid", manifest_args!());
 
-// To be continued
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "min_next_bid",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success()
+        .output(1)
+    }
+
+    /// Returns the listing metadata of the auction as a `(title, description, image_url)` tuple.
+    pub fn get_listing_metadata(
+        &mut self,
+        english_auction: ComponentAddress,
+    ) -> (String, String, Option<String>) {
+        let manifest = ManifestBuilder::new().call_method(
+            english_auction,
+            "get_listing_metadata",
+            manifest_args!(),
+        );
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "get_listing_metadata",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success()
+        .output(1)
+    }
+
+    pub fn outcome(&mut self, english_auction: ComponentAddress) -> AuctionOutcome {
+        let manifest =
+            ManifestBuilder::new().call_method(english_auction, "outcome", manifest_args!());
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "outcome",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success()
+        .output(1)
+    }
+
+    pub fn claim_nft(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        bidders_badge: ResourceAddress,
+    ) -> TransactionReceipt {
+        self.call_with_bidders_badge(bidder, english_auction, bidders_badge, "claim_nfts")
+    }
+
+    pub fn claim_fill(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        bidders_badge: ResourceAddress,
+    ) -> TransactionReceipt {
+        self.call_with_bidders_badge(bidder, english_auction, bidders_badge, "claim_fill")
+    }
+
+    pub fn fill_amount(
+        &mut self,
+        english_auction: ComponentAddress,
+        bidder: NonFungibleLocalId,
+    ) -> Decimal {
+        let manifest = ManifestBuilder::new().call_method(
+            english_auction,
+            "fill_amount",
+            manifest_args!(bidder),
+        );
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "fill_amount",
+            &NetworkDefinition::simulator(),
+        )
+        .expect_commit_success()
+        .output(1)
+    }
+
+    pub fn cancel_bid(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        bidders_badge: ResourceAddress,
+    ) -> TransactionReceipt {
+        self.call_with_bidders_badge(bidder, english_auction, bidders_badge, "cancel_bid")
+    }
+
+    pub fn cancel_auction(
+        &mut self,
+        english_auction: &EnglishAuctionAddresses,
+    ) -> TransactionReceipt {
+        self.call_as_seller(english_auction, "cancel_auction", manifest_args!())
+    }
+
+    pub fn withdraw_payment(
+        &mut self,
+        english_auction: &EnglishAuctionAddresses,
+    ) -> TransactionReceipt {
+        self.call_as_seller(english_auction, "withdraw_payment", manifest_args!())
+    }
+
+    fn call_as_seller(
+        &mut self,
+        english_auction: &EnglishAuctionAddresses,
+        method_name: &str,
+        args: ManifestArgs,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(
+                self.account.account_address,
+                english_auction.ownership_badge,
+                dec!(1),
+            )
+            .call_method(english_auction.component, method_name, args)
+            .deposit_batch(self.account.account_address);
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            method_name,
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    /// Creates a new account and securifies it, depositing its owner badge into the test account. Returns the address
+    /// of the new account and the global ID of its owner badge.
+    pub fn new_securified_account(&mut self) -> (ComponentAddress, NonFungibleGlobalId) {
+        let account = self.new_account();
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                account.account_address,
+                ACCOUNT_SECURIFY_IDENT,
+                manifest_args!(),
+            )
+            .deposit_batch(self.account.account_address);
+        self.test_runner
+            .execute_manifest_ignoring_fee(
+                manifest.build(),
+                vec![
+                    NonFungibleGlobalId::from_public_key(&account.public_key),
+                    NonFungibleGlobalId::from_public_key(&self.account.public_key),
+                ],
+            )
+            .expect_commit_success();
+
+        let owner_badge = NonFungibleGlobalId::new(
+            ACCOUNT_OWNER_BADGE,
+            NonFungibleLocalId::bytes(account.account_address.as_node_id().0).unwrap(),
+        );
+        (account.account_address, owner_badge)
+    }
+
+    /// Calls a seller only method with a proof of the given account owner badge, which must be held by the test
+    /// account.
+    pub fn call_as_account_owner(
+        &mut self,
+        english_auction: ComponentAddress,
+        owner_badge: &NonFungibleGlobalId,
+        method_name: &str,
+        args: ManifestArgs,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                self.account.account_address,
+                owner_badge.resource_address(),
+                btreeset!(owner_badge.local_id().clone()),
+            )
+            .call_method(english_auction, method_name, args)
+            .deposit_batch(self.account.account_address);
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            method_name,
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn set_beneficiary(
+        &mut self,
+        english_auction: &EnglishAuctionAddresses,
+        beneficiary: ComponentAddress,
+    ) -> TransactionReceipt {
+        self.call_as_seller(
+            english_auction,
+            "set_beneficiary",
+            manifest_args!(beneficiary),
+        )
+    }
+
+    pub fn reveal_reserve(
+        &mut self,
+        english_auction: &EnglishAuctionAddresses,
+        reserve: Decimal,
+        nonce: &str,
+    ) -> TransactionReceipt {
+        self.call_as_seller(
+            english_auction,
+            "reveal_reserve",
+            manifest_args!(reserve, nonce.to_string()),
+        )
+    }
+
+    /// Withdraws the settlement fees as the given account, presenting its `fee_collector_badge`.
+    pub fn withdraw_fees(
+        &mut self,
+        collector: &Account,
+        english_auction: ComponentAddress,
+        fee_collector_badge: ResourceAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(
+                collector.account_address,
+                fee_collector_badge,
+                dec!(1),
+            )
+            .call_method(english_auction, "withdraw_fees", manifest_args!())
+            .deposit_batch(collector.account_address);
+
+        self.execute_manifest_ignoring_fee_as(
+            collector.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "withdraw_fees",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn emergency_withdraw(
+        &mut self,
+        english_auction: &EnglishAuctionAddresses,
+    ) -> TransactionReceipt {
+        self.call_as_seller(english_auction, "emergency_withdraw", manifest_args!())
+    }
+
+    fn call_with_bidders_badge(
+        &mut self,
+        bidder: &Account,
+        english_auction: ComponentAddress,
+        bidders_badge: ResourceAddress,
+        method_name: &str,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(bidder.account_address, bidders_badge, dec!(1))
+            .take_all_from_worktop(bidders_badge, "bidders_badge")
+            .call_method_with_name_lookup(english_auction, method_name, |lookup| {
+                (lookup.bucket("bidders_badge"),)
+            })
+            .deposit_batch(bidder.account_address);
+
+        self.execute_manifest_ignoring_fee_as(
+            bidder.public_key,
+            manifest.object_names(),
+            manifest.build(),
+            method_name,
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn balance(&mut self, account: &Account, resource_address: ResourceAddress) -> Decimal {
+        self.test_runner
+            .get_component_balance(account.account_address, resource_address)
+    }
+
+    pub fn seller_balance(&mut self, resource_address: ResourceAddress) -> Decimal {
+        self.test_runner
+            .get_component_balance(self.account.account_address, resource_address)
+    }
+}
+
+/// Returns the commitment to a hidden reserve which the auction expects: the SHA-256 hash of the SBOR encoded
+/// `(reserve, nonce)` tuple.
+fn reserve_commitment(reserve: Decimal, nonce: &str) -> [u8; 32] {
+    Sha256::digest(scrypto_encode(&(reserve, nonce.to_string())).unwrap()).into()
+}
+
+#[test]
+fn instantiate_english_auction() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+
+    let receipt = test_environment
+        .instantiate_english_auction(non_fungible_token, AuctionArguments::default());
+
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn bid_in_wrong_currency_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let other_token = test_environment.test_runner.create_fungible_resource(
+        dec!(1000),
+        18,
+        bidder.account_address,
+    );
+
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    let receipt =
+        test_environment.place_bid(&bidder, english_auction.component, other_token, dec!(10));
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("Bids are only allowed in")
+    });
+}
+
+#[test]
+fn empty_bid_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    let receipt = test_environment.place_bid(&bidder, english_auction.component, XRD, dec!(0));
+
+    receipt.expect_specific_failure(|error| format!("{:?}", error).contains("empty bid"));
+}
+
+#[test]
+fn single_bid_auction_settles_to_the_sole_bidder() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    let bidder_xrd = test_environment.balance(&bidder, XRD);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    test_environment
+        .claim_nft(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.balance(&bidder, non_fungible_token),
+        dec!(1)
+    );
+    assert_eq!(
+        bidder_xrd
+            .checked_sub(test_environment.balance(&bidder, XRD))
+            .unwrap(),
+        dec!(100)
+    );
+    assert_eq!(
+        test_environment
+            .seller_balance(XRD)
+            .checked_sub(seller_xrd)
+            .unwrap(),
+        dec!(100)
+    );
+}
+
+#[test]
+fn two_bid_auction_goes_to_the_higher_bid_and_refunds_the_lower() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let low_bidder = test_environment.new_account();
+    let high_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    let low_bidder_xrd = test_environment.balance(&low_bidder, XRD);
+    let high_bidder_xrd = test_environment.balance(&high_bidder, XRD);
+    test_environment
+        .place_bid(&low_bidder, english_auction.component, XRD, dec!(50))
+        .expect_commit_success();
+    test_environment
+        .place_bid(&high_bidder, english_auction.component, XRD, dec!(80))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    // The lower bidder can not claim the NFT but gets their full bid back.
+    test_environment
+        .claim_nft(
+            &low_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_failure();
+    test_environment
+        .cancel_bid(
+            &low_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    test_environment
+        .claim_nft(
+            &high_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+
+    assert_eq!(test_environment.balance(&low_bidder, XRD), low_bidder_xrd);
+    assert_eq!(
+        test_environment.balance(&low_bidder, non_fungible_token),
+        dec!(0)
+    );
+    assert_eq!(
+        high_bidder_xrd
+            .checked_sub(test_environment.balance(&high_bidder, XRD))
+            .unwrap(),
+        dec!(80)
+    );
+    assert_eq!(
+        test_environment.balance(&high_bidder, non_fungible_token),
+        dec!(1)
+    );
+}
+
+#[test]
+fn expired_auction_without_bids_returns_the_nft() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let nfts_before = test_environment.seller_balance(non_fungible_token);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    test_environment
+        .cancel_auction(&english_auction)
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.seller_balance(non_fungible_token),
+        nfts_before
+    );
+}
+
+#[test]
+fn proxy_bid_beats_lower_manual_bid_without_paying_its_maximum() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let proxy_bidder = test_environment.new_account();
+    let manual_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    let proxy_bidder_xrd = test_environment.balance(&proxy_bidder, XRD);
+    test_environment
+        .place_proxy_bid(&proxy_bidder, english_auction.component, dec!(100))
+        .expect_commit_success();
+    test_environment
+        .place_bid(&manual_bidder, english_auction.component, XRD, dec!(50))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    test_environment
+        .claim_nft(
+            &proxy_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+
+    // The proxy bidder only pays one increment above the competing bid and gets the rest of the escrow back.
+    assert_eq!(
+        test_environment.balance(&proxy_bidder, non_fungible_token),
+        dec!(1)
+    );
+    assert_eq!(
+        proxy_bidder_xrd
+            .checked_sub(test_environment.balance(&proxy_bidder, XRD))
+            .unwrap(),
+        dec!(51)
+    );
+    assert_eq!(
+        test_environment
+            .seller_balance(XRD)
+            .checked_sub(seller_xrd)
+            .unwrap(),
+        dec!(51)
+    );
+}
+
+#[test]
+fn winning_bid_below_min_seller_proceeds_voids_the_sale() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let nfts_before = test_environment.seller_balance(non_fungible_token);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            min_seller_proceeds: dec!(100),
+            ..Default::default()
+        },
+    );
+
+    // A bid below the seller's floor is rejected upfront, but a proxy bid may still settle below it.
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(50))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The bid is below the minimum next bid")
+        });
+    let bidder_xrd = test_environment.balance(&bidder, XRD);
+    test_environment
+        .place_proxy_bid(&bidder, english_auction.component, dec!(50))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    // The highest bid does not cover the seller's floor, so there is no winner.
+    test_environment
+        .claim_nft(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_failure();
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_failure();
+
+    test_environment
+        .cancel_bid(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    test_environment
+        .cancel_auction(&english_auction)
+        .expect_commit_success();
+
+    assert_eq!(test_environment.balance(&bidder, XRD), bidder_xrd);
+    assert_eq!(
+        test_environment.balance(&bidder, non_fungible_token),
+        dec!(0)
+    );
+    assert_eq!(
+        test_environment.seller_balance(non_fungible_token),
+        nfts_before
+    );
+}
+
+#[test]
+fn bid_history_lists_the_bids_in_order() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let first_bidder = test_environment.new_account();
+    let second_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+    let start_epoch = test_environment.test_runner.get_current_epoch();
+
+    test_environment
+        .place_bid(&first_bidder, english_auction.component, XRD, dec!(10))
+        .expect_commit_success();
+    test_environment.advance_epochs(1);
+    test_environment
+        .place_bid(&second_bidder, english_auction.component, XRD, dec!(20))
+        .expect_commit_success();
+    test_environment.advance_epochs(1);
+    test_environment
+        .place_bid(&first_bidder, english_auction.component, XRD, dec!(30))
+        .expect_commit_success();
+
+    let bid_history = test_environment.bid_history(english_auction.component);
+
+    assert_eq!(
+        bid_history
+            .iter()
+            .map(|(_, amount, epoch)| (*amount, *epoch))
+            .collect::<Vec<_>>(),
+        vec![
+            (dec!(10), start_epoch),
+            (dec!(20), start_epoch.after(1).unwrap()),
+            (dec!(30), start_epoch.after(2).unwrap()),
+        ]
+    );
+    assert!(bid_history
+        .iter()
+        .all(|(bidder, _, _)| bidder.resource_address() == english_auction.bidders_badge));
+}
+
+#[test]
+fn bid_history_hides_the_maximum_of_proxy_bids() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let manual_bidder = test_environment.new_account();
+    let proxy_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    test_environment
+        .place_bid(&manual_bidder, english_auction.component, XRD, dec!(50))
+        .expect_commit_success();
+    test_environment
+        .place_proxy_bid(&proxy_bidder, english_auction.component, dec!(100))
+        .expect_commit_success();
+    test_environment
+        .increase_bid(
+            &proxy_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+            dec!(20),
+        )
+        .expect_commit_success();
+
+    // Both the proxy bid and its increase are recorded with the amount bid on behalf of the bidder.
+    let bid_history = test_environment.bid_history(english_auction.component);
+    assert_eq!(
+        bid_history
+            .iter()
+            .map(|(_, amount, _)| *amount)
+            .collect::<Vec<_>>(),
+        vec![dec!(50), dec!(51), dec!(51)]
+    );
+}
+
+#[test]
+fn settlement_fee_is_retained_and_the_seller_receives_the_rest() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let collector = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let fee_collector_badge = test_environment.test_runner.create_fungible_resource(
+        dec!(1),
+        0,
+        collector.account_address,
+    );
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_fee_percent: dec!(10),
+            fee_collector: Some(fee_collector_badge),
+            ..Default::default()
+        },
+    );
+
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .seller_balance(XRD)
+            .checked_sub(seller_xrd)
+            .unwrap(),
+        dec!(90)
+    );
+
+    let collector_xrd = test_environment.balance(&collector, XRD);
+    test_environment
+        .withdraw_fees(&collector, english_auction.component, fee_collector_badge)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .balance(&collector, XRD)
+            .checked_sub(collector_xrd)
+            .unwrap(),
+        dec!(10)
+    );
+}
+
+#[test]
+fn settlement_fees_are_only_released_to_the_fee_collector_once_the_sale_is_final() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let collector = test_environment.new_account();
+    let arbiter = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let fee_collector_badge = test_environment.test_runner.create_fungible_resource(
+        dec!(1),
+        0,
+        collector.account_address,
+    );
+    let arbiter_badge =
+        test_environment
+            .test_runner
+            .create_fungible_resource(dec!(1), 0, arbiter.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_fee_percent: dec!(10),
+            fee_collector: Some(fee_collector_badge),
+            dispute_window_epochs: 5,
+            dispute_arbiter: Some(arbiter_badge),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    // The fees are held while the sale may still be disputed, and the seller can never withdraw them.
+    test_environment
+        .withdraw_fees(&collector, english_auction.component, fee_collector_badge)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The fees can only be withdrawn once the sale is final")
+        });
+    test_environment.advance_epochs(5);
+    test_environment
+        .call_as_seller(&english_auction, "withdraw_fees", manifest_args!())
+        .expect_commit_failure();
+
+    let collector_xrd = test_environment.balance(&collector, XRD);
+    test_environment
+        .withdraw_fees(&collector, english_auction.component, fee_collector_badge)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.balance(&collector, XRD),
+        collector_xrd.checked_add(dec!(10)).unwrap()
+    );
+}
+
+#[test]
+fn settlement_fee_without_a_fee_collector_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let receipt = test_environment.instantiate_english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_fee_percent: dec!(10),
+            ..Default::default()
+        },
+    );
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error)
+            .contains("A fee collector is required when there is a settlement fee")
+    });
+}
+
+#[test]
+fn unsold_auction_retains_no_settlement_fee() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let collector = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let fee_collector_badge = test_environment.test_runner.create_fungible_resource(
+        dec!(1),
+        0,
+        collector.account_address,
+    );
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_fee_percent: dec!(10),
+            fee_collector: Some(fee_collector_badge),
+            ..Default::default()
+        },
+    );
+
+    test_environment.advance_epochs(10);
+    let collector_xrd = test_environment.balance(&collector, XRD);
+    test_environment
+        .withdraw_fees(&collector, english_auction.component, fee_collector_badge)
+        .expect_commit_success();
+
+    assert_eq!(test_environment.balance(&collector, XRD), collector_xrd);
+}
+
+#[test]
+fn emergency_withdraw_is_only_possible_after_the_rescue_delay() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let losing_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let nfts_before = test_environment.seller_balance(non_fungible_token);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+    let losing_bidder_xrd = test_environment.balance(&losing_bidder, XRD);
+    test_environment
+        .place_bid(&losing_bidder, english_auction.component, XRD, dec!(50))
+        .expect_commit_success();
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+
+    // Neither the winner nor the losing bidder ever come back.
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10_000);
+    test_environment
+        .emergency_withdraw(&english_auction)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("[Emergency Withdraw]: Can only be called")
+        });
+
+    test_environment.advance_epochs(1);
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .emergency_withdraw(&english_auction)
+        .expect_commit_success();
+
+    // The seller only gets their proceeds, while the NFT and the losing bid stay with their owners.
+    assert_eq!(
+        test_environment
+            .seller_balance(XRD)
+            .checked_sub(seller_xrd)
+            .unwrap(),
+        dec!(100)
+    );
+    assert_eq!(
+        test_environment.seller_balance(non_fungible_token),
+        nfts_before.checked_sub(dec!(1)).unwrap()
+    );
+
+    test_environment
+        .claim_nft(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.balance(&bidder, non_fungible_token),
+        dec!(1)
+    );
+    test_environment
+        .cancel_bid(
+            &losing_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.balance(&losing_bidder, XRD),
+        losing_bidder_xrd
+    );
+}
+
+#[test]
+fn emergency_withdraw_of_an_unsettled_auction_returns_the_nfts() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let nfts_before = test_environment.seller_balance(non_fungible_token);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+    let bidder_xrd = test_environment.balance(&bidder, XRD);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+
+    // Nobody settles the auction, so the rescue cancels it instead of selling the NFT.
+    test_environment.advance_epochs(10 + 10_001);
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .emergency_withdraw(&english_auction)
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.seller_balance(non_fungible_token),
+        nfts_before
+    );
+    assert_eq!(test_environment.seller_balance(XRD), seller_xrd);
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::CanceledBySeller
+    );
+
+    test_environment
+        .cancel_bid(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&bidder, XRD), bidder_xrd);
+}
+
+#[test]
+fn proceeds_go_to_the_beneficiary() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let beneficiary = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+    test_environment
+        .set_beneficiary(&english_auction, beneficiary.account_address)
+        .expect_commit_success();
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    let seller_xrd = test_environment.seller_balance(XRD);
+    let beneficiary_xrd = test_environment.balance(&beneficiary, XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+
+    assert_eq!(test_environment.seller_balance(XRD), seller_xrd);
+    assert_eq!(
+        test_environment
+            .balance(&beneficiary, XRD)
+            .checked_sub(beneficiary_xrd)
+            .unwrap(),
+        dec!(100)
+    );
+}
+
+#[test]
+fn proceeds_rejected_by_the_beneficiary_go_to_the_seller() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let beneficiary = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    // Only accounts may receive the proceeds.
+    test_environment
+        .set_beneficiary(&english_auction, english_auction.component)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error)
+                .contains("[Set Beneficiary]: The beneficiary must be an account.")
+        });
+
+    test_environment.reject_deposits(&beneficiary);
+    test_environment
+        .set_beneficiary(&english_auction, beneficiary.account_address)
+        .expect_commit_success();
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    // The beneficiary can no longer be replaced, but the rejected proceeds are returned to the seller.
+    test_environment
+        .set_beneficiary(&english_auction, test_environment.account.account_address)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error)
+                .contains("[Set Beneficiary]: The beneficiary can only be changed")
+        });
+    let seller_xrd = test_environment.seller_balance(XRD);
+    let beneficiary_xrd = test_environment.balance(&beneficiary, XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&beneficiary, XRD), beneficiary_xrd);
+    assert_eq!(
+        test_environment.seller_balance(XRD),
+        seller_xrd.checked_add(dec!(100)).unwrap()
+    );
+}
+
+#[test]
+fn min_next_bid_follows_the_auction() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let fee_collector_badge = test_environment.test_runner.create_fungible_resource(
+        dec!(1),
+        0,
+        test_environment.account.account_address,
+    );
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            min_seller_proceeds: dec!(45),
+            settlement_fee_percent: dec!(10),
+            fee_collector: Some(fee_collector_badge),
+            ..Default::default()
+        },
+    );
+
+    // Without bids, the minimum is the bid which nets the seller their minimum proceeds after the fee.
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(50)
+    );
+
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(60))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(61)
+    );
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(60)
+    );
+}
+
+#[test]
+fn min_next_bid_follows_a_percentage_bid_increment() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let other_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            bid_increment: BidIncrement::Percentage(dec!(10)),
+            ..Default::default()
+        },
+    );
+
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(60))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(66)
+    );
+
+    // A bid below the increment is rejected, even though it exceeds the highest bid.
+    test_environment
+        .place_bid(&other_bidder, english_auction.component, XRD, dec!(65))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("[Bid]: The bid is below the minimum next bid of 66.")
+        });
+    test_environment
+        .place_bid(&other_bidder, english_auction.component, XRD, dec!(66))
+        .expect_commit_success();
+}
+
+#[test]
+fn proxy_bid_is_raised_by_the_bid_increment() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let proxy_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            bid_increment: BidIncrement::Absolute(dec!(5)),
+            ..Default::default()
+        },
+    );
+
+    test_environment
+        .place_proxy_bid(&proxy_bidder, english_auction.component, dec!(100))
+        .expect_commit_success();
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(60))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(70)
+    );
+}
+
+#[test]
+fn bid_increment_must_be_positive() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+
+    for bid_increment in [
+        BidIncrement::Absolute(dec!(0)),
+        BidIncrement::Percentage(dec!(-1)),
+    ] {
+        test_environment
+            .instantiate_english_auction(
+                non_fungible_token,
+                AuctionArguments {
+                    bid_increment,
+                    ..Default::default()
+                },
+            )
+            .expect_specific_failure(|error| {
+                format!("{:?}", error)
+                    .contains("[Instantiation]: The bid increment must be positive.")
+            });
+    }
+}
+
+#[test]
+fn min_next_bid_covers_the_settlement_reward_and_the_revealed_reserve() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let fee_collector_badge = test_environment.test_runner.create_fungible_resource(
+        dec!(1),
+        0,
+        test_environment.account.account_address,
+    );
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            min_seller_proceeds: dec!(40),
+            settlement_fee_percent: dec!(10),
+            fee_collector: Some(fee_collector_badge),
+            settlement_reward: dec!(5),
+            reserve_commitment: Some(reserve_commitment(dec!(80), "nonce")),
+            ..Default::default()
+        },
+    );
+
+    // The seller's minimum proceeds and the settlement reward are grossed up by the fee.
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(50)
+    );
+
+    // Once revealed, the reserve raises the quote.
+    test_environment
+        .reveal_reserve(&english_auction, dec!(80), "nonce")
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(80)
+    );
+
+    // Bids below the quote are rejected, and a bid at the quote wins the auction.
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(79))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The bid is below the minimum next bid")
+        });
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(80))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    assert!(matches!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::SoldTo { amount, .. } if amount == dec!(80)
+    ));
+}
+
+#[test]
+fn listing_metadata_is_stored_intact() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            title: "Rare candy".to_string(),
+            description: "A single piece of rare candy.".to_string(),
+            image_url: Some("https://example.com/candy.png".to_string()),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        test_environment.get_listing_metadata(english_auction.component),
+        (
+            "Rare candy".to_string(),
+            "A single piece of rare candy.".to_string(),
+            Some("https://example.com/candy.png".to_string())
+        )
+    );
+}
+
+#[test]
+fn overly_long_title_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let receipt = test_environment.instantiate_english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            title: "a".repeat(101),
+            ..Default::default()
+        },
+    );
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("[Instantiation]: The title can not be longer than")
+    });
+}
+
+#[test]
+fn outbid_bidder_is_refunded_to_their_account_when_refunds_are_pushed() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let low_bidder = test_environment.new_account();
+    let high_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            push_refunds: true,
+            ..Default::default()
+        },
+    );
+
+    let low_bidder_xrd = test_environment.balance(&low_bidder, XRD);
+    test_environment
+        .place_bid_with_refund_account(&low_bidder, english_auction.component, dec!(50))
+        .expect_commit_success();
+    assert_eq!(
+        low_bidder_xrd
+            .checked_sub(test_environment.balance(&low_bidder, XRD))
+            .unwrap(),
+        dec!(50)
+    );
+
+    // Being outbid pushes the bid back to the bidder's account right away.
+    test_environment
+        .place_bid(&high_bidder, english_auction.component, XRD, dec!(80))
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&low_bidder, XRD), low_bidder_xrd);
+}
+
+#[test]
+fn refund_account_which_is_not_an_account_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            push_refunds: true,
+            ..Default::default()
+        },
+    );
+
+    // A refund pushed to a component without a deposit method would fail every later bid.
+    test_environment
+        .place_bid_refunding_to(
+            &bidder,
+            english_auction.component,
+            dec!(50),
+            english_auction.component,
+        )
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("[Bid]: The refund account must be an account.")
+        });
+}
+
+#[test]
+fn outbid_bidder_withdraws_their_bid_when_refunds_are_not_pushed() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let low_bidder = test_environment.new_account();
+    let high_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+
+    let low_bidder_xrd = test_environment.balance(&low_bidder, XRD);
+    test_environment
+        .place_bid_with_refund_account(&low_bidder, english_auction.component, dec!(50))
+        .expect_commit_success();
+    test_environment
+        .place_bid(&high_bidder, english_auction.component, XRD, dec!(80))
+        .expect_commit_success();
+    assert_eq!(
+        low_bidder_xrd
+            .checked_sub(test_environment.balance(&low_bidder, XRD))
+            .unwrap(),
+        dec!(50)
+    );
+
+    test_environment
+        .cancel_bid(
+            &low_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&low_bidder, XRD), low_bidder_xrd);
+}
+
+#[test]
+fn rejected_refund_push_falls_back_to_withdrawing_the_bid() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let low_bidder = test_environment.new_account();
+    let high_bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            push_refunds: true,
+            ..Default::default()
+        },
+    );
+
+    test_environment.reject_deposits(&low_bidder);
+    let low_bidder_xrd = test_environment.balance(&low_bidder, XRD);
+    test_environment
+        .place_bid_with_refund_account(&low_bidder, english_auction.component, dec!(50))
+        .expect_commit_success();
+
+    // The rejected push does not fail the outbidding bid, the funds stay in the auction instead.
+    test_environment
+        .place_bid(&high_bidder, english_auction.component, XRD, dec!(80))
+        .expect_commit_success();
+    assert_eq!(
+        low_bidder_xrd
+            .checked_sub(test_environment.balance(&low_bidder, XRD))
+            .unwrap(),
+        dec!(50)
+    );
+
+    test_environment
+        .cancel_bid(
+            &low_bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&low_bidder, XRD), low_bidder_xrd);
+}
+
+#[test]
+fn late_bids_stop_extending_the_auction_after_max_extensions() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            relative_ending_epoch: 10,
+            max_extensions: 2,
+            ..Default::default()
+        },
+    );
+
+    // Each last-second bid pushes the end to 5 epochs after the bid, until the cap is reached.
+    test_environment.advance_epochs(8);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(10))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.extension_count(english_auction.component),
+        1
+    );
+
+    test_environment.advance_epochs(4);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(20))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.extension_count(english_auction.component),
+        2
+    );
+
+    test_environment.advance_epochs(4);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(30))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.extension_count(english_auction.component),
+        2
+    );
+
+    // The ending epoch is firm now, so the auction ends 5 epochs after the second extension.
+    test_environment.advance_epochs(1);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(40))
+        .expect_commit_failure();
+}
+
+#[test]
+fn first_settler_receives_the_settlement_reward() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let settler = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_reward: dec!(5),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+
+    let settler_xrd = test_environment.balance(&settler, XRD);
+    test_environment
+        .settle_for_reward(&settler, english_auction.component)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .balance(&settler, XRD)
+            .checked_sub(settler_xrd)
+            .unwrap(),
+        dec!(5)
+    );
+
+    // The auction is already settled, so nobody can collect the reward a second time.
+    test_environment
+        .settle_for_reward(&settler, english_auction.component)
+        .expect_commit_failure();
+
+    // The reward comes out of the seller's proceeds.
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment
+            .seller_balance(XRD)
+            .checked_sub(seller_xrd)
+            .unwrap(),
+        dec!(95)
+    );
+}
+
+#[test]
+fn settling_an_auction_without_bids_pays_no_reward() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let settler = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_reward: dec!(5),
+            ..Default::default()
+        },
+    );
+    test_environment.advance_epochs(10);
+
+    let settler_xrd = test_environment.balance(&settler, XRD);
+    test_environment
+        .settle_for_reward(&settler, english_auction.component)
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&settler, XRD), settler_xrd);
+}
+
+#[test]
+fn bids_below_the_starting_price_are_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            starting_price: dec!(20),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        test_environment.min_next_bid(english_auction.component),
+        dec!(20)
+    );
+
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(15))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The bid is below the starting price")
+        });
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(20))
+        .expect_commit_success();
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(25))
+        .expect_commit_success();
+}
+
+#[test]
+fn negative_starting_price_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let receipt = test_environment.instantiate_english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            starting_price: dec!(-1),
+            ..Default::default()
+        },
+    );
+
+    receipt.expect_commit_failure();
+}
+
+#[test]
+fn outcome_is_pending_until_the_auction_ends_and_sold_after() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(50))
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::Pending
+    );
+
+    // The outcome is known as soon as the auction ends, and settling it does not change it.
+    test_environment.advance_epochs(10);
+    let outcome = test_environment.outcome(english_auction.component);
+    match &outcome {
+        AuctionOutcome::SoldTo { bidder, amount } => {
+            assert_eq!(bidder.resource_address(), english_auction.bidders_badge);
+            assert_eq!(*amount, dec!(50));
+        }
+        _ => panic!("Expected the auction to be sold, got {:?}", outcome),
+    }
+
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    assert_eq!(test_environment.outcome(english_auction.component), outcome);
+}
+
+#[test]
+fn outcome_of_an_auction_without_bids_is_unsold() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+    test_environment.advance_epochs(10);
+
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::UnsoldNoBids
+    );
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::UnsoldNoBids
+    );
+}
+
+#[test]
+fn outcome_of_an_auction_below_the_minimum_proceeds_is_unsold() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            min_seller_proceeds: dec!(100),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_proxy_bid(&bidder, english_auction.component, dec!(50))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::UnsoldReserveNotMet
+    );
+}
+
+#[test]
+fn outcome_of_a_canceled_auction_is_canceled_by_seller() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction =
+        test_environment.english_auction(non_fungible_token, AuctionArguments::default());
+    test_environment
+        .cancel_auction(&english_auction)
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::CanceledBySeller
+    );
+}
+
+#[test]
+fn early_settlement_rebates_part_of_the_settlement_fee() {
+    let mut net_fees = Vec::new();
+    for epochs_after_end in [3, 6] {
+        let mut test_environment = TestEnvironment::instantiate_test();
+        let bidder = test_environment.new_account();
+        let collector = test_environment.new_account();
+
+        let non_fungible_token = test_environment
+            .test_runner
+            .create_non_fungible_resource(test_environment.account.account_address);
+        let fee_collector_badge = test_environment.test_runner.create_fungible_resource(
+            dec!(1),
+            0,
+            collector.account_address,
+        );
+        let english_auction = test_environment.english_auction(
+            non_fungible_token,
+            AuctionArguments {
+                settlement_fee_percent: dec!(10),
+                fee_collector: Some(fee_collector_badge),
+                settlement_grace_epochs: 5,
+                early_settlement_rebate_percent: dec!(50),
+                ..Default::default()
+            },
+        );
+        test_environment
+            .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+            .expect_commit_success();
+        test_environment.advance_epochs(10 + epochs_after_end);
+        test_environment
+            .settle(english_auction.component)
+            .expect_commit_success();
+
+        let collector_xrd = test_environment.balance(&collector, XRD);
+        test_environment
+            .withdraw_fees(&collector, english_auction.component, fee_collector_badge)
+            .expect_commit_success();
+        net_fees.push(
+            test_environment
+                .balance(&collector, XRD)
+                .checked_sub(collector_xrd)
+                .unwrap(),
+        );
+    }
+
+    // Within the grace window half of the fee is rebated, after it the full fee is kept.
+    assert_eq!(net_fees, vec![dec!(5), dec!(10)]);
+}
+
+#[test]
+fn bid_with_excess_precision_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let payment_token = test_environment.test_runner.create_fungible_resource(
+        dec!(1000),
+        2,
+        bidder.account_address,
+    );
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            accepted_payment_token: payment_token,
+            ..Default::default()
+        },
+    );
+
+    test_environment
+        .place_proxy_bid_with(
+            &bidder,
+            english_auction.component,
+            payment_token,
+            dec!("10.005"),
+            dec!(11),
+        )
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("bid precision exceeds token divisibility")
+        });
+    test_environment
+        .place_proxy_bid_with(
+            &bidder,
+            english_auction.component,
+            payment_token,
+            dec!("10.01"),
+            dec!(11),
+        )
+        .expect_commit_success();
+}
+
+#[test]
+fn undisputed_proceeds_are_released_after_the_dispute_window() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let arbiter = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let arbiter_badge =
+        test_environment
+            .test_runner
+            .create_fungible_resource(dec!(1), 0, arbiter.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            dispute_window_epochs: 5,
+            dispute_arbiter: Some(arbiter_badge),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error)
+                .contains("The payment is held until the dispute window has passed")
+        });
+
+    test_environment.advance_epochs(5);
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.seller_balance(XRD),
+        seller_xrd.checked_add(dec!(100)).unwrap()
+    );
+    test_environment
+        .raise_dispute(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The dispute window has passed")
+        });
+}
+
+#[test]
+fn disputed_sale_resolves_to_a_winner_refund() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let arbiter = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let arbiter_badge =
+        test_environment
+            .test_runner
+            .create_fungible_resource(dec!(1), 0, arbiter.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            dispute_window_epochs: 5,
+            dispute_arbiter: Some(arbiter_badge),
+            ..Default::default()
+        },
+    );
+    let nfts_before = test_environment.seller_balance(non_fungible_token);
+    let bidder_xrd = test_environment.balance(&bidder, XRD);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+
+    test_environment
+        .raise_dispute(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+
+    // The proceeds and the NFTs are frozen while the dispute is pending, even after the window has passed.
+    test_environment.advance_epochs(5);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The payment is held until the dispute is resolved")
+        });
+    test_environment
+        .claim_nft(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("NFTs can not be claimed while the sale is disputed")
+        });
+
+    // Only the arbiter may resolve the dispute.
+    test_environment
+        .resolve_dispute(&bidder, english_auction.component, arbiter_badge, true)
+        .expect_commit_failure();
+    test_environment
+        .resolve_dispute(&arbiter, english_auction.component, arbiter_badge, true)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::RefundedAfterDispute
+    );
+
+    test_environment
+        .cancel_bid(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&bidder, XRD), bidder_xrd);
+
+    test_environment
+        .cancel_auction(&english_auction)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.seller_balance(non_fungible_token),
+        nfts_before.checked_add(dec!(1)).unwrap()
+    );
+}
+
+#[test]
+fn disputed_refund_returns_the_whole_winning_bid() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let arbiter = test_environment.new_account();
+    let collector = test_environment.new_account();
+    let settler = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let arbiter_badge =
+        test_environment
+            .test_runner
+            .create_fungible_resource(dec!(1), 0, arbiter.account_address);
+    let fee_collector_badge = test_environment.test_runner.create_fungible_resource(
+        dec!(1),
+        0,
+        collector.account_address,
+    );
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_fee_percent: dec!(10),
+            fee_collector: Some(fee_collector_badge),
+            settlement_reward: dec!(5),
+            dispute_window_epochs: 5,
+            dispute_arbiter: Some(arbiter_badge),
+            ..Default::default()
+        },
+    );
+    let bidder_xrd = test_environment.balance(&bidder, XRD);
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+
+    // The settlement reward is held in escrow while the sale may be disputed.
+    let settler_xrd = test_environment.balance(&settler, XRD);
+    test_environment
+        .settle_for_reward(&settler, english_auction.component)
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&settler, XRD), settler_xrd);
+
+    test_environment
+        .raise_dispute(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    test_environment
+        .resolve_dispute(&arbiter, english_auction.component, arbiter_badge, true)
+        .expect_commit_success();
+
+    // The winner gets their bid back including the settlement fee and reward, which nobody else receives.
+    test_environment
+        .cancel_bid(
+            &bidder,
+            english_auction.component,
+            english_auction.bidders_badge,
+        )
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&bidder, XRD), bidder_xrd);
+
+    let collector_xrd = test_environment.balance(&collector, XRD);
+    test_environment
+        .withdraw_fees(&collector, english_auction.component, fee_collector_badge)
+        .expect_commit_success();
+    assert_eq!(test_environment.balance(&collector, XRD), collector_xrd);
+    assert_eq!(test_environment.balance(&settler, XRD), settler_xrd);
+}
+
+#[test]
+fn escrowed_settlement_reward_is_paid_when_the_proceeds_are_released() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let arbiter = test_environment.new_account();
+    let settler = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let arbiter_badge =
+        test_environment
+            .test_runner
+            .create_fungible_resource(dec!(1), 0, arbiter.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            settlement_reward: dec!(5),
+            dispute_window_epochs: 5,
+            dispute_arbiter: Some(arbiter_badge),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    let settler_xrd = test_environment.balance(&settler, XRD);
+    test_environment
+        .settle_for_reward(&settler, english_auction.component)
+        .expect_commit_success();
+
+    test_environment.advance_epochs(5);
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.balance(&settler, XRD),
+        settler_xrd.checked_add(dec!(5)).unwrap()
+    );
+    assert_eq!(
+        test_environment.seller_balance(XRD),
+        seller_xrd.checked_add(dec!(95)).unwrap()
+    );
+}
+
+#[test]
+fn proceeds_are_converted_into_the_payout_token() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let (dex, payout_token) = test_environment.instantiate_mock_dex(dec!(2));
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            payout_conversion: Some(PayoutConversion {
+                payout_token,
+                dex,
+                min_payout: dec!(150),
+            }),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+    assert_eq!(test_environment.seller_balance(payout_token), dec!(200));
+    assert_eq!(test_environment.seller_balance(XRD), seller_xrd);
+}
+
+#[test]
+fn payout_conversion_with_excessive_slippage_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let (dex, payout_token) = test_environment.instantiate_mock_dex(dec!(2));
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            payout_conversion: Some(PayoutConversion {
+                payout_token,
+                dex,
+                min_payout: dec!(250),
+            }),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_specific_failure(|error| format!("{:?}", error).contains("Slippage too high"));
+    assert_eq!(test_environment.seller_balance(payout_token), dec!(0));
+}
+
+#[test]
+fn multi_fill_auction_fills_the_highest_bids_first() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidders: Vec<Account> = (0..3).map(|_| test_environment.new_account()).collect();
+
+    let lot_token = test_environment.test_runner.create_fungible_resource(
+        dec!(100),
+        18,
+        test_environment.account.account_address,
+    );
+    let english_auction = test_environment.tranche_auction(
+        lot_token,
+        dec!(100),
+        AuctionArguments {
+            multi_fill: true,
+            ..Default::default()
+        },
+    );
+
+    // Bidding 3, 2 and 1 XRD per unit for 50, 30 and 40 units of a lot of 100.
+    let bids = [
+        (dec!(150), dec!(50)),
+        (dec!(60), dec!(30)),
+        (dec!(40), dec!(40)),
+    ];
+    let xrd_before: Vec<Decimal> = bidders
+        .iter()
+        .map(|bidder| test_environment.balance(bidder, XRD))
+        .collect();
+    for (bidder, (amount, quantity)) in bidders.iter().zip(bids) {
+        test_environment
+            .bid_for_quantity(bidder, english_auction.component, amount, quantity)
+            .expect_commit_success();
+    }
+    let badges: Vec<NonFungibleLocalId> = test_environment
+        .bid_history(english_auction.component)
+        .into_iter()
+        .map(|(bidder, _, _)| bidder.local_id().clone())
+        .collect();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::SoldInTranches {
+            sold: dec!(100),
+            amount: dec!(230)
+        }
+    );
+
+    // The top two bids are filled fully and the third only for the 20 units left, paying 20 of its 40 XRD.
+    let fills = [dec!(50), dec!(30), dec!(20)];
+    let costs = [dec!(150), dec!(60), dec!(20)];
+    for (index, bidder) in bidders.iter().enumerate() {
+        assert_eq!(
+            test_environment.fill_amount(english_auction.component, badges[index].clone()),
+            fills[index]
+        );
+        test_environment
+            .claim_fill(
+                bidder,
+                english_auction.component,
+                english_auction.bidders_badge,
+            )
+            .expect_commit_success();
+        assert_eq!(test_environment.balance(bidder, lot_token), fills[index]);
+        assert_eq!(
+            test_environment.balance(bidder, XRD),
+            xrd_before[index].checked_sub(costs[index]).unwrap()
+        );
+    }
+
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .withdraw_payment(&english_auction)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.seller_balance(XRD),
+        seller_xrd.checked_add(dec!(230)).unwrap()
+    );
+}
+
+#[test]
+fn quantity_finer_than_the_lot_divisibility_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let lot_token = test_environment.test_runner.create_fungible_resource(
+        dec!(100),
+        2,
+        test_environment.account.account_address,
+    );
+    let english_auction = test_environment.tranche_auction(
+        lot_token,
+        dec!(100),
+        AuctionArguments {
+            multi_fill: true,
+            ..Default::default()
+        },
+    );
+
+    test_environment
+        .bid_for_quantity(&bidder, english_auction.component, dec!(20), dec!("10.005"))
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The quantity exceeds the divisibility of the lot")
+        });
+    test_environment
+        .bid_for_quantity(&bidder, english_auction.component, dec!(20), dec!("10.01"))
+        .expect_commit_success();
+}
+
+#[test]
+fn revealed_reserve_voids_a_sale_below_it() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            reserve_commitment: Some(reserve_commitment(dec!(150), "nonce")),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment
+        .reveal_reserve(&english_auction, dec!(150), "nonce")
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::UnsoldReserveNotMet
+    );
+}
+
+#[test]
+fn unrevealed_reserve_does_not_apply() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            reserve_commitment: Some(reserve_commitment(dec!(150), "nonce")),
+            ..Default::default()
+        },
+    );
+    test_environment
+        .place_bid(&bidder, english_auction.component, XRD, dec!(100))
+        .expect_commit_success();
+
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction.component)
+        .expect_commit_success();
+    assert!(matches!(
+        test_environment.outcome(english_auction.component),
+        AuctionOutcome::SoldTo { amount, .. } if amount == dec!(100)
+    ));
+}
+
+#[test]
+fn mismatched_reserve_reveal_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment.english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            reserve_commitment: Some(reserve_commitment(dec!(150), "nonce")),
+            ..Default::default()
+        },
+    );
+
+    test_environment
+        .reveal_reserve(&english_auction, dec!(100), "nonce")
+        .expect_specific_failure(|error| {
+            format!("{:?}", error).contains("The reserve and nonce do not match the commitment")
+        });
+}
+
+#[test]
+fn recorded_seller_account_performs_the_seller_actions() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let (seller_account, seller_badge) = test_environment.new_securified_account();
+    let (_, other_badge) = test_environment.new_securified_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let receipt = test_environment.instantiate_english_auction(
+        non_fungible_token,
+        AuctionArguments {
+            seller_account: Some(seller_account),
+            ..Default::default()
+        },
+    );
+    let commit = receipt.expect_commit_success();
+    // Without an ownership badge, the bidder's badge is the only new resource.
+    assert_eq!(commit.new_resource_addresses().len(), 1);
+    let english_auction = commit.new_component_addresses()[0];
+
+    test_environment
+        .place_bid(&bidder, english_auction, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction)
+        .expect_commit_success();
+
+    // The owner of a different account can not act as the seller.
+    test_environment
+        .call_as_account_owner(
+            english_auction,
+            &other_badge,
+            "withdraw_payment",
+            manifest_args!(),
+        )
+        .expect_commit_failure();
+
+    let seller_xrd = test_environment.seller_balance(XRD);
+    test_environment
+        .call_as_account_owner(
+            english_auction,
+            &seller_badge,
+            "withdraw_payment",
+            manifest_args!(),
+        )
+        .expect_commit_success();
+    assert_eq!(
+        test_environment.seller_balance(XRD),
+        seller_xrd.checked_add(dec!(100)).unwrap()
+    );
+}
+
+#[test]
+fn recorded_virtual_seller_account_is_authorized_by_its_key() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let bidder = test_environment.new_account();
+    let (seller_key, _, seller_account) = test_environment.test_runner.new_virtual_account();
+    let (other_key, _, _) = test_environment.test_runner.new_virtual_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let english_auction = test_environment
+        .instantiate_english_auction(
+            non_fungible_token,
+            AuctionArguments {
+                seller_account: Some(seller_account),
+                ..Default::default()
+            },
+        )
+        .expect_commit_success()
+        .new_component_addresses()[0];
+    test_environment
+        .place_bid(&bidder, english_auction, XRD, dec!(100))
+        .expect_commit_success();
+    test_environment.advance_epochs(10);
+    test_environment
+        .settle(english_auction)
+        .expect_commit_success();
+
+    // Only a signature with the key the seller account was derived from authorizes the seller.
+    let seller_xrd = test_environment
+        .test_runner
+        .get_component_balance(seller_account, XRD);
+    for (public_key, succeeds) in [(other_key, false), (seller_key, true)] {
+        let manifest = ManifestBuilder::new()
+            .call_method(english_auction, "withdraw_payment", manifest_args!())
+            .deposit_batch(seller_account);
+        let receipt = test_environment.execute_manifest_ignoring_fee_as(
+            public_key,
+            manifest.object_names(),
+            manifest.build(),
+            "withdraw_payment",
+            &NetworkDefinition::simulator(),
+        );
+        if succeeds {
+            receipt.expect_commit_success();
+        } else {
+            receipt.expect_commit_failure();
+        }
+    }
+    assert_eq!(
+        test_environment
+            .test_runner
+            .get_component_balance(seller_account, XRD),
+        seller_xrd.checked_add(dec!(100)).unwrap()
+    );
+}
+
+#[test]
+fn only_the_recorded_seller_account_can_cancel_the_auction() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let (seller_account, seller_badge) = test_environment.new_securified_account();
+    let (_, other_badge) = test_environment.new_securified_account();
+
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let nfts_before = test_environment.seller_balance(non_fungible_token);
+    let english_auction = test_environment
+        .instantiate_english_auction(
+            non_fungible_token,
+            AuctionArguments {
+                seller_account: Some(seller_account),
+                ..Default::default()
+            },
+        )
+        .expect_commit_success()
+        .new_component_addresses()[0];
+
+    test_environment
+        .call_as_account_owner(
+            english_auction,
+            &other_badge,
+            "cancel_auction",
+            manifest_args!(),
+        )
+        .expect_commit_failure();
+    test_environment
+        .call_as_account_owner(
+            english_auction,
+            &seller_badge,
+            "cancel_auction",
+            manifest_args!(),
+        )
+        .expect_commit_success();
+
+    assert_eq!(
+        test_environment.seller_balance(non_fungible_token),
+        nfts_before
+    );
+}
@@ -0,0 +1,222 @@
+use radix_engine::transaction::TransactionReceipt;
+use scrypto::prelude::*;
+use scrypto_unit::*;
+use transaction::{
+    builder::ManifestBuilder, manifest::decompiler::ManifestObjectNames,
+    prelude::TransactionManifestV1,
+};
+
+pub struct Account {
+    public_key: Secp256k1PublicKey,
+    account_address: ComponentAddress,
+}
+
+/// The configuration of an auction, mirroring the `AuctionConfig` of the blueprint.
+#[derive(ManifestSbor)]
+pub struct AuctionConfig {
+    min_seller_proceeds: Decimal,
+    settlement_fee_percent: Decimal,
+    fee_collector: Option<ResourceAddress>,
+    listing_metadata: (String, String, Option<String>),
+    push_refunds: bool,
+    max_extensions: u64,
+    settlement_reward: Decimal,
+    starting_price: Decimal,
+    settlement_grace_epochs: u64,
+    early_settlement_rebate_percent: Decimal,
+    dispute_window_epochs: u64,
+    dispute_arbiter: Option<ResourceAddress>,
+    payout_conversion: Option<(ResourceAddress, ComponentAddress, Decimal)>,
+    multi_fill: bool,
+    reserve_commitment: Option<[u8; 32]>,
+    seller_account: Option<ComponentAddress>,
+    bid_increment: BidIncrement,
+}
+
+/// How much a new bid must exceed the highest bid by, mirroring the `BidIncrement` of the blueprint.
+#[derive(ManifestSbor)]
+pub enum BidIncrement {
+    Absolute(Decimal),
+}
+
+pub struct TestEnvironment {
+    test_runner: DefaultTestRunner,
+    account: Account,
+    package_address: PackageAddress,
+}
+
+impl TestEnvironment {
+    pub fn instantiate_test() -> Self {
+        let mut test_runner = TestRunnerBuilder::new().build();
+
+        // Create an account
+        let (public_key, _private_key, account_address) = test_runner.new_allocated_account();
+
+        let account = Account {
+            public_key,
+            account_address,
+        };
+
+        let package_address = test_runner.compile_and_publish(this_package!());
+
+        Self {
+            test_runner,
+            account,
+            package_address,
+        }
+    }
+
+    pub fn execute_manifest_ignoring_fee(
+        &mut self,
+        manifest_names: ManifestObjectNames,
+        manifest: TransactionManifestV1,
+        name: &str,
+        network: &NetworkDefinition,
+    ) -> TransactionReceipt {
+        dump_manifest_to_file_system(
+            manifest_names,
+            &manifest,
+            "./transaction_manifest/marketplace",
+            Some(name),
+            network,
+        )
+        .err();
+
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(
+                &self.account.public_key,
+            )],
+        )
+    }
+
+    /// Instantiates a new marketplace and returns the addresses of the component and its admin badge.
+    pub fn instantiate_marketplace(
+        &mut self,
+        allowed_collections: Vec<ResourceAddress>,
+    ) -> (ComponentAddress, ResourceAddress) {
+        let manifest = ManifestBuilder::new()
+            .call_function(
+                self.package_address,
+                "Marketplace",
+                "instantiate_marketplace",
+                manifest_args!(allowed_collections),
+            )
+            .deposit_batch(self.account.account_address);
+
+        let receipt = self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "instantiate_marketplace",
+            &NetworkDefinition::simulator(),
+        );
+        let commit = receipt.expect_commit_success();
+
+        (
+            commit.new_component_addresses()[0],
+            commit.new_resource_addresses()[0],
+        )
+    }
+
+    pub fn add_allowed_collection(
+        &mut self,
+        marketplace: ComponentAddress,
+        admin_badge: ResourceAddress,
+        collection: ResourceAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_amount(self.account.account_address, admin_badge, dec!(1))
+            .call_method(
+                marketplace,
+                "add_allowed_collection",
+                manifest_args!(collection),
+            );
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "add_allowed_collection",
+            &NetworkDefinition::simulator(),
+        )
+    }
+
+    pub fn instantiate_english_auction(
+        &mut self,
+        marketplace: ComponentAddress,
+        non_fungible_tokens: ResourceAddress,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_non_fungibles_from_account(
+                self.account.account_address,
+                non_fungible_tokens,
+                btreeset!(NonFungibleLocalId::integer(1)),
+            )
+            .take_all_from_worktop(non_fungible_tokens, "bucket")
+            .call_method_with_name_lookup(marketplace, "instantiate_english_auction", |lookup| {
+                let config = AuctionConfig {
+                    min_seller_proceeds: dec!(0),
+                    settlement_fee_percent: dec!(0),
+                    fee_collector: None,
+                    listing_metadata: ("Marketplace auction".to_string(), String::new(), None),
+                    push_refunds: false,
+                    max_extensions: 0,
+                    settlement_reward: dec!(0),
+                    starting_price: dec!(0),
+                    settlement_grace_epochs: 0,
+                    early_settlement_rebate_percent: dec!(0),
+                    dispute_window_epochs: 0,
+                    dispute_arbiter: None,
+                    payout_conversion: None,
+                    multi_fill: false,
+                    reserve_commitment: None,
+                    seller_account: None,
+                    bid_increment: BidIncrement::Absolute(dec!(1)),
+                };
+                (vec![lookup.bucket("bucket")], XRD, 10u64, config)
+            })
+            .deposit_batch(self.account.account_address);
+
+        self.execute_manifest_ignoring_fee(
+            manifest.object_names(),
+            manifest.build(),
+            "instantiate_english_auction",
+            &NetworkDefinition::simulator(),
+        )
+    }
+}
+
+#[test]
+fn auction_for_an_allowed_collection_can_be_instantiated() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+
+    let (marketplace, admin_badge) = test_environment.instantiate_marketplace(vec![]);
+    test_environment
+        .add_allowed_collection(marketplace, admin_badge, non_fungible_token)
+        .expect_commit_success();
+
+    let receipt = test_environment.instantiate_english_auction(marketplace, non_fungible_token);
+
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn auction_for_a_disallowed_collection_is_rejected() {
+    let mut test_environment = TestEnvironment::instantiate_test();
+    let allowed_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+    let non_fungible_token = test_environment
+        .test_runner
+        .create_non_fungible_resource(test_environment.account.account_address);
+
+    let (marketplace, _) = test_environment.instantiate_marketplace(vec![allowed_token]);
+
+    let receipt = test_environment.instantiate_english_auction(marketplace, non_fungible_token);
+
+    receipt.expect_specific_failure(|error| {
+        format!("{:?}", error).contains("collection not approved")
+    });
+}
@@ -0,0 +1,40 @@
+use scrypto::prelude::*;
+
+/// The rule satisfied by the owner of the given `account`: the key a virtual account's address was
+/// derived from, or the account's owner badge once it is securified. Panics if the given address is
+/// not an account.
+pub fn account_owner_rule(account: ComponentAddress) -> AccessRule {
+    let node_id = account.as_node_id();
+    assert!(
+        node_id
+            .entity_type()
+            .map_or(false, |entity_type| entity_type.is_global_account()),
+        "{:?} is not an account",
+        account
+    );
+
+    let owner_badge = NonFungibleGlobalId::new(
+        ACCOUNT_OWNER_BADGE,
+        NonFungibleLocalId::bytes(node_id.0.to_vec()).unwrap(),
+    );
+    let signature_badge = match node_id.entity_type() {
+        Some(EntityType::GlobalVirtualSecp256k1Account) => Some(SECP256K1_SIGNATURE_VIRTUAL_BADGE),
+        Some(EntityType::GlobalVirtualEd25519Account) => Some(ED25519_SIGNATURE_VIRTUAL_BADGE),
+        _ => None,
+    };
+    match signature_badge {
+        Some(signature_badge) => {
+            let owner_key = NonFungibleGlobalId::new(
+                signature_badge,
+                NonFungibleLocalId::bytes(node_id.0[1..].to_vec()).unwrap(),
+            );
+            rule!(require(owner_key) || require(owner_badge))
+        }
+        None => rule!(require(owner_badge)),
+    }
+}
+
+/// Panics unless the owner of the given `account` is present, see `account_owner_rule`.
+pub fn assert_account_owner(account: ComponentAddress) {
+    Runtime::assert_access_rule(account_owner_rule(account));
+}
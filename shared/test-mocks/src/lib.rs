@@ -0,0 +1,8 @@
+//! Stand-ins for external components, which the tests of the examples publish alongside their own
+//! package. This package is only meant for tests and should never be deployed.
+
+/// A minimal DEX used to exercise swaps in tests.
+mod mock_dex;
+
+/// A minimal compliance component used to exercise the sale reporting of the regulated token in tests.
+mod mock_compliance;
@@ -0,0 +1,35 @@
+use scrypto::prelude::*;
+
+// A stand-in for an issuer's compliance component, used to test the sale reporting of the
+// RegulatedToken. It logs every reported sale, or vetoes all of them if it was told to.
+#[blueprint]
+mod mock_compliance {
+    struct MockCompliance {
+        veto: bool,
+        reported_sales: Vec<(ComponentAddress, Decimal, Decimal)>,
+    }
+
+    impl MockCompliance {
+        /// Creates a new mock compliance component, which rejects every sale if `veto` is set
+        pub fn instantiate_mock_compliance(veto: bool) -> Global<MockCompliance> {
+            Self {
+                veto,
+                reported_sales: Vec::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn report_sale(&mut self, buyer: ComponentAddress, quantity: Decimal, price: Decimal) {
+            assert!(!self.veto, "Sale vetoed by compliance");
+
+            self.reported_sales.push((buyer, quantity, price));
+        }
+
+        /// The `(buyer, quantity, price)` of every sale reported so far, oldest first
+        pub fn reported_sales(&self) -> Vec<(ComponentAddress, Decimal, Decimal)> {
+            self.reported_sales.clone()
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use scrypto::prelude::*;
+
+// A stand-in for a real DEX, used to test swaps such as the treasury swap of the RegulatedToken or
+// the payout conversion of the EnglishAuction.
+// It swaps any input for its output token at a fixed rate, with no liquidity curve or fees.
+#[blueprint]
+mod mock_dex {
+    struct MockDex {
+        input_tokens: KeyValueStore<ResourceAddress, Vault>,
+        output_tokens: Vault,
+        rate: Decimal,
+    }
+
+    impl MockDex {
+        /// Creates a new mock DEX which pays out `rate` of the `output_tokens` per input token
+        pub fn instantiate_mock_dex(output_tokens: Bucket, rate: Decimal) -> Global<MockDex> {
+            assert!(rate > dec!("0"), "The rate must be positive");
+
+            Self {
+                input_tokens: KeyValueStore::new(),
+                output_tokens: Vault::with_bucket(output_tokens),
+                rate,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn swap(&mut self, input_bucket: Bucket) -> Bucket {
+            let output_amount = input_bucket.amount().checked_mul(self.rate).unwrap();
+
+            let resource_address = input_bucket.resource_address();
+            if self.input_tokens.get(&resource_address).is_none() {
+                self.input_tokens
+                    .insert(resource_address, Vault::new(resource_address));
+            }
+            self.input_tokens
+                .get_mut(&resource_address)
+                .unwrap()
+                .put(input_bucket);
+
+            self.output_tokens.take(output_amount)
+        }
+    }
+}